@@ -0,0 +1,37 @@
+use console_engine::backend::HeadlessBackend;
+use console_engine::{pixel, Color, ConsoleEngine};
+
+fn main() {
+    // no terminal is needed: the headless backend lets this run in CI
+    let mut engine =
+        ConsoleEngine::init_with_backend(HeadlessBackend::new(30, 8), 30, 8, 20).unwrap();
+
+    let path = std::env::temp_dir().join("console_engine-demo.cast");
+    let file = std::fs::File::create(&path).unwrap();
+    engine
+        .start_asciicast(std::io::BufWriter::new(file), Some("bouncing ball"))
+        .unwrap();
+
+    // record 5 seconds of a ball bouncing around
+    let (mut x, mut y, mut dx, mut dy) = (1, 1, 1, 1);
+    for _ in 0..100 {
+        engine.wait_frame();
+        engine.clear_screen();
+        engine.rect(0, 0, 29, 7, pixel::pxl_fg('#', Color::Blue));
+        engine.set_pxl(x, y, pixel::pxl_fg('O', Color::Yellow));
+        engine.draw();
+        if x + dx <= 0 || x + dx >= 29 {
+            dx = -dx;
+        }
+        if y + dy <= 0 || y + dy >= 7 {
+            dy = -dy;
+        }
+        x += dx;
+        y += dy;
+    }
+    engine.stop_asciicast().unwrap();
+    drop(engine);
+
+    println!("Recorded into {}", path.display());
+    println!("Play it with: asciinema play {}", path.display());
+}
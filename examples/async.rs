@@ -0,0 +1,47 @@
+use console_engine::{events::Event, KeyCode};
+use futures_util::future::{select, Either};
+use futures_util::io::AllowStdIo;
+use futures_util::StreamExt;
+use std::time::Duration;
+
+// simulates a network request taking some time
+async fn fetch_status(request: u32) -> String {
+    futures_timer::Delay::new(Duration::from_millis(700)).await;
+    format!("response #{}", request)
+}
+
+fn main() {
+    // the engine works with any async runtime (tokio, smol, async-std...),
+    // here we use a minimal executor
+    futures_executor::block_on(async {
+        let mut engine = console_engine::ConsoleEngine::init(40, 4, 30).unwrap();
+        let mut stdout = AllowStdIo::new(std::io::stdout());
+        let mut request = 1;
+        let mut status = String::from("waiting...");
+        let mut pending = Box::pin(fetch_status(request));
+
+        loop {
+            // wait for either the next engine event or the end of the request
+            let event = match select(engine.event_stream().next(), pending.as_mut()).await {
+                Either::Left((event, _)) => event.unwrap(),
+                Either::Right((response, _)) => {
+                    status = response;
+                    request += 1;
+                    pending = Box::pin(fetch_status(request));
+                    continue;
+                }
+            };
+            match event {
+                Event::Frame => {
+                    engine.clear_screen();
+                    engine.print(0, 0, &format!("Last status: {}", status));
+                    engine.print(0, 1, &format!("Frame: {}", engine.frame_count));
+                    engine.print(0, 3, "press 'q' to quit");
+                    engine.draw_async(&mut stdout).await.unwrap();
+                }
+                Event::Key(key) if key.code == KeyCode::Char('q') => break,
+                _ => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,24 @@
+use console_engine::screen;
+use console_engine::{Attribute, Attributes, Color};
+
+fn main() {
+    let mut scr = screen::Screen::new(20, 7);
+
+    scr.print_attr(0, 0, "Bold", Attribute::Bold.into());
+    scr.print_attr(0, 1, "Italic", Attribute::Italic.into());
+    scr.print_attr(0, 2, "Underlined", Attribute::Underlined.into());
+    scr.print_attr(0, 3, "Reverse", Attribute::Reverse.into());
+    scr.print_attr(0, 4, "Dim", Attribute::Dim.into());
+    scr.print_attr(0, 5, "Strikethrough", Attribute::CrossedOut.into());
+    scr.print_fbg_attr(
+        0,
+        6,
+        "Bold & underlined",
+        Color::Cyan,
+        Color::Reset,
+        Attributes::from(Attribute::Bold) | Attribute::Underlined,
+    );
+
+    // print the screen to the terminal
+    scr.draw();
+}
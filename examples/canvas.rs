@@ -0,0 +1,60 @@
+use console_engine::canvas::{Canvas, CanvasMode};
+use console_engine::Color;
+use console_engine::KeyCode;
+
+fn main() {
+    // initializes a screen filling the terminal of at least 40x20 of size with a target of 30 frame per second
+    let mut engine = console_engine::ConsoleEngine::init_fill_require(40, 20, 30).unwrap();
+
+    let mut mode = CanvasMode::Braille;
+    // main loop, be aware that you'll have to break it because ctrl+C is captured
+    loop {
+        engine.wait_frame(); // wait for next frame + capture inputs
+        engine.check_resize(); // resize the terminal if its size has changed
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            break;
+        }
+        // switch between Braille and half blocks
+        if engine.is_key_pressed(KeyCode::Char(' ')) {
+            mode = match mode {
+                CanvasMode::Braille => CanvasMode::HalfBlock,
+                CanvasMode::HalfBlock => CanvasMode::Braille,
+            };
+        }
+        engine.clear_screen();
+
+        // the canvas covers the whole screen except the header
+        let mut canvas = Canvas::new(engine.get_width(), engine.get_height() - 1, mode);
+        let width = canvas.get_dot_width() as i32;
+        let height = canvas.get_dot_height() as i32;
+
+        // a filled circle in the background, upper and lower halves have their own colors in half block mode
+        canvas.fill_circle(width / 2, height / 2, height as u32 / 3, Color::DarkBlue);
+        canvas.circle(width / 2, height / 2, height as u32 / 3, Color::Blue);
+
+        // plot two moving sine curves, joining each point to the previous one
+        let time = engine.frame_count as f32 / 10.0;
+        for (color, speed, amplitude) in [(Color::Green, 1.0, 0.4), (Color::Yellow, 2.0, 0.25)] {
+            let mut last = None;
+            for x in 0..width {
+                let value = ((x as f32 / width as f32) * 12.0 + time * speed).sin();
+                let y = height / 2 - (value * amplitude * height as f32) as i32;
+                if let Some((last_x, last_y)) = last {
+                    canvas.line(last_x, last_y, x, y, color);
+                }
+                last = Some((x, y));
+            }
+        }
+
+        engine.print(
+            0,
+            0,
+            &format!(
+                "{:?} canvas ({}x{} dots) - space: switch mode, q: quit",
+                mode, width, height
+            ),
+        );
+        engine.blit_canvas(0, 1, &canvas);
+        engine.draw();
+    }
+}
@@ -0,0 +1,81 @@
+use console_engine::pixel;
+use console_engine::Color;
+use console_engine::ConsoleEngine;
+use console_engine::KeyCode;
+
+// static pattern of the background, every row being different from the blank screen
+fn background_pixel(x: i32, y: i32) -> pixel::Pixel {
+    let pattern = format!("{:03} ", y % 1000);
+    let chr = pattern.chars().nth(x as usize % 4).unwrap();
+    pixel::pxl_fg(chr, Color::DarkGrey)
+}
+
+fn draw_background(engine: &mut ConsoleEngine) {
+    for y in 1..engine.get_height() as i32 {
+        for x in 0..engine.get_width() as i32 {
+            engine.set_pxl(x, y, background_pixel(x, y));
+        }
+    }
+}
+
+fn main() {
+    // initializes a screen filling the terminal with a target of 120 frame per second
+    let mut engine = ConsoleEngine::init_fill(120).unwrap();
+    let (mut x, mut y, mut dx, mut dy) = (1, 1, 1, 1);
+    // when enabled, the whole screen is cleared and drawn again every frame
+    let mut redraw_everything = false;
+    // draw times of the last frames, to display a stable average
+    let mut draw_times = std::collections::VecDeque::new();
+    draw_background(&mut engine);
+    // main loop, be aware that you'll have to break it because ctrl+C is captured
+    loop {
+        engine.wait_frame(); // wait for next frame + capture inputs
+        if engine.get_resize().is_some() {
+            engine.check_resize();
+            draw_background(&mut engine);
+        }
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            break;
+        }
+        if engine.is_key_pressed(KeyCode::Char(' ')) {
+            redraw_everything = !redraw_everything;
+            draw_times.clear();
+        }
+
+        // every row is modified, even if the terminal output stays the same
+        if redraw_everything {
+            engine.clear_screen();
+            draw_background(&mut engine);
+        }
+
+        // move the ball: only the rows it crosses are modified
+        let (width, height) = (engine.get_width() as i32, engine.get_height() as i32);
+        engine.set_pxl(x, y, background_pixel(x, y));
+        if x + dx < 0 || x + dx >= width {
+            dx = -dx;
+        }
+        if y + dy < 1 || y + dy >= height {
+            dy = -dy;
+        }
+        x += dx;
+        y += dy;
+        engine.set_pxl(x, y, pixel::pxl_fg('O', Color::Yellow));
+
+        // the engine only scans and stores the rows modified since the last frame
+        draw_times.push_back(engine.get_draw_time().as_micros());
+        if draw_times.len() > 60 {
+            draw_times.pop_front();
+        }
+        let average = draw_times.iter().sum::<u128>() / draw_times.len() as u128;
+        let stats = format!(
+            "FPS: {:.0} | draw: {}µs average, {} bytes | redraw everything: {} (space to toggle)",
+            engine.get_fps(),
+            average,
+            engine.get_draw_size(),
+            redraw_everything
+        );
+        engine.print(0, 0, &format!("{:width$}", stats, width = width as usize));
+
+        engine.draw(); // draw the screen
+    }
+}
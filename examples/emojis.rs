@@ -1,14 +1,20 @@
 use console_engine::{pixel, KeyCode};
 
 fn main() {
-    let mut engine = console_engine::ConsoleEngine::init(6, 5, 3).unwrap();
+    let mut engine = console_engine::ConsoleEngine::init(14, 7, 3).unwrap();
     loop {
         engine.wait_frame();
         engine.clear_screen();
 
         // draw a rectangle with an emoji inside
-        engine.rect(0, 0, 5, 4, pixel::pxl('#'));
-        engine.set_pxl(2, 2, pixel::pxl('👍'));
+        engine.rect(0, 0, 13, 6, pixel::pxl('#'));
+        engine.set_pxl(2, 1, pixel::pxl('👍'));
+        // emojis made of several characters (modifiers, flags, zero width joiner sequences)
+        engine.set_pxl(5, 1, pixel::pxl_str("👍🏽"));
+        // printed text advances by the width of each grapheme
+        engine.print(2, 3, "🇫🇷👨‍👩‍👧‍👦❤️");
+        // combining accents stay on their letter
+        engine.print(2, 5, "Cafe\u{301}!");
 
         if engine.is_key_pressed(KeyCode::Char('q')) {
             break;
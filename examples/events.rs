@@ -1,10 +1,33 @@
 use console_engine::{events::Event, KeyCode};
 
 fn main() {
-    // initializes the engine
-    let mut engine = console_engine::ConsoleEngine::init(60, 3, 3).unwrap();
+    // initializes the engine, also reporting focus changes and pasted text,
+    // and reading the input from a thread so custom events are received right away
+    let mut engine = console_engine::ConsoleEngine::builder()
+        .size(60, 3)
+        .target_fps(3)
+        .focus_reporting(true)
+        .bracketed_paste(true)
+        .input_thread(true)
+        .build()
+        .unwrap();
     let mut message = String::new();
 
+    // send a custom event from another thread every second
+    let sender = engine.event_sender();
+    std::thread::spawn(move || {
+        for second in 1u32.. {
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            // stop once the engine has been dropped
+            if sender.send(second).is_err() {
+                break;
+            }
+        }
+    });
+
+    // also schedule a timer firing every 5 seconds
+    engine.set_interval(std::time::Duration::from_secs(5), 0);
+
     loop {
         // Poll next event
         match engine.poll() {
@@ -36,6 +59,28 @@ fn main() {
             Event::Resize(w, h) => {
                 message = format!("Resize: {:?}, {:?}", w, h);
             }
+
+            // Terminal gained or lost the focus
+            Event::Focus(focused) => {
+                message = format!("Focus: {:?}", focused);
+            }
+
+            // Some text has been pasted
+            Event::Paste(text) => {
+                message = format!("Paste: {:?}", text);
+            }
+
+            // A custom event has been sent
+            Event::User(event) => {
+                if let Some(second) = event.downcast_ref::<u32>() {
+                    message = format!("User: {} seconds elapsed", second);
+                }
+            }
+
+            // A timer fired
+            Event::Timer(id) => {
+                message = format!("Timer: {}", id);
+            }
         }
     }
 }
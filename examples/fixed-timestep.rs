@@ -0,0 +1,73 @@
+use console_engine::pixel;
+use console_engine::timing::FixedTimestep;
+use console_engine::{Color, KeyCode};
+
+fn main() {
+    // initializes a screen filling the terminal with a target of 30 frames per second
+    let mut engine = console_engine::ConsoleEngine::init_fill(30).unwrap();
+    // the physics runs 100 times per second, whatever the frame rate is
+    let mut timestep = FixedTimestep::from_rate(100);
+
+    // ball position and speed, in characters and characters per second
+    let mut position = (2.0, 2.0);
+    let mut previous_position = position;
+    let mut speed = (25.0, 0.0);
+    let gravity = 40.0;
+
+    // main loop, be aware that you'll have to break it because ctrl+C is captured
+    loop {
+        engine.wait_frame(); // wait for next frame + capture inputs
+        engine.check_resize(); // resize the terminal if its size has changed
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            // if the user presses 'q' :
+            break; // exits app
+        }
+        if engine.is_key_pressed(KeyCode::Char(' ')) {
+            // kick the ball
+            speed.1 = -30.0;
+        }
+
+        // run as many updates as the elapsed time allows
+        timestep.accumulate(engine.get_delta_time());
+        let width = engine.get_width() as f64 - 1.0;
+        let height = engine.get_height() as f64 - 1.0;
+        while timestep.step() {
+            let dt = timestep.get_step().as_secs_f64();
+            previous_position = position;
+            speed.1 += gravity * dt;
+            position.0 += speed.0 * dt;
+            position.1 += speed.1 * dt;
+            // bounce on the borders
+            if position.0 < 0.0 || position.0 > width {
+                speed.0 = -speed.0;
+                position.0 = position.0.clamp(0.0, width);
+            }
+            if position.1 > height {
+                speed.1 = -speed.1 * 0.9;
+                position.1 = height;
+            }
+        }
+
+        // draw the ball between the last two updates
+        let alpha = timestep.get_alpha();
+        let x = previous_position.0 * (1.0 - alpha) + position.0 * alpha;
+        let y = previous_position.1 * (1.0 - alpha) + position.1 * alpha;
+
+        engine.clear_screen();
+        engine.set_pxl(
+            x.round() as i32,
+            y.round() as i32,
+            pixel::pxl_fg('O', Color::Yellow),
+        );
+        engine.print(
+            0,
+            0,
+            &format!(
+                "FPS: {:.0} | delta: {}ms | space: kick, q: quit",
+                engine.get_fps(),
+                engine.get_delta_time().as_millis()
+            ),
+        );
+        engine.draw(); // draw the screen
+    }
+}
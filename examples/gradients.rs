@@ -0,0 +1,69 @@
+use console_engine::color::{self, Gradient, GradientDirection};
+use console_engine::pixel;
+use console_engine::Color;
+use console_engine::KeyCode;
+
+fn main() {
+    // initializes a screen of 60x20 characters with a target of 30 frame per second
+    let mut engine = console_engine::ConsoleEngine::init(60, 20, 30).unwrap();
+    let mut health = 100;
+    // main loop, be aware that you'll have to break it because ctrl+C is captured
+    loop {
+        engine.wait_frame(); // wait for next frame + capture inputs
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            break;
+        }
+        if engine.is_key_pressed(KeyCode::Left) {
+            health = (health - 5).max(0);
+        }
+        if engine.is_key_pressed(KeyCode::Right) {
+            health = (health + 5).min(100);
+        }
+        engine.clear_screen();
+
+        // a health bar, whose color depends on the remaining health
+        let health_color = Gradient::health().sample(health as f32 / 100.0);
+        engine.print(0, 0, &format!("Health: {:3}% (arrows to change)", health));
+        engine.fill_rect(0, 1, 49, 1, pixel::pxl_bg(' ', Color::DarkGrey));
+        if health > 0 {
+            engine.fill_rect(0, 1, health / 2 - 1, 1, pixel::pxl_bg(' ', health_color));
+        }
+
+        // the named palettes
+        let palettes = [
+            ("heat", Gradient::heat()),
+            ("health", Gradient::health()),
+            ("rainbow", Gradient::rainbow()),
+            ("viridis", Gradient::viridis()),
+            ("grayscale", Gradient::grayscale()),
+        ];
+        for (i, (name, gradient)) in palettes.iter().enumerate() {
+            let y = 3 + i as i32;
+            engine.print(0, y, name);
+            engine.fill_gradient(10, y, 39, y, gradient, GradientDirection::Horizontal);
+        }
+
+        // a heatmap slowly pulsing, with a radial gradient
+        let pulse = (engine.frame_count as f32 / 10.0).sin() * 0.2 + 0.8;
+        let heat = Gradient::from_stops(&[
+            (0.0, color::lerp(Color::White, Color::Yellow, 1.0 - pulse)),
+            (0.5 * pulse, Color::Red),
+            (1.0, Color::Black),
+        ]);
+        engine.fill_gradient(42, 3, 59, 12, &heat, GradientDirection::Radial);
+
+        // a vertical sky, and the color wheel with every hue
+        let sky = Gradient::new(&[color::hsl(220.0, 0.8, 0.3), color::hsl(200.0, 0.9, 0.8)]);
+        engine.fill_gradient(0, 9, 39, 13, &sky, GradientDirection::Vertical);
+        for x in 0..60 {
+            let hue = x as f32 * 6.0;
+            engine.set_pxl(x, 15, pixel::pxl_bg(' ', color::hsv(hue, 1.0, 1.0)));
+            // the same hue, with a transparent black over it
+            let shaded = color::blend(color::hsv(hue, 1.0, 1.0), Color::Black, 0.5);
+            engine.set_pxl(x, 16, pixel::pxl_bg(' ', shaded));
+        }
+
+        engine.print(0, 19, "press 'q' to quit");
+        engine.draw(); // draw the screen
+    }
+}
@@ -1,3 +1,4 @@
+use console_engine::color::Gradient;
 use console_engine::pixel;
 use console_engine::Color;
 use console_engine::KeyCode;
@@ -16,6 +17,8 @@ fn draw_graph(engine: &mut console_engine::ConsoleEngine, values: [u8; MAX_VALUE
     let ceiling = engine.get_height() as i32 - 3;
     let step = engine.get_width() as f32 / MAX_VALUES as f32;
     let mut last_position = 0;
+    // the higher the value, the hotter the color
+    let gradient = Gradient::heat();
     // for each values in the dataset
     #[allow(clippy::needless_range_loop)]
     for i in 0..MAX_VALUES - 1 {
@@ -29,7 +32,7 @@ fn draw_graph(engine: &mut console_engine::ConsoleEngine, values: [u8; MAX_VALUE
             2 + ceiling - last_position,
             ((1 + i) as f32 * step) as i32,
             2 + ceiling - position,
-            pixel::pxl('*'),
+            pixel::pxl_fg('*', gradient.sample(0.3 + 0.7 * value as f32 / 255f32)),
         );
 
         // keep the position for the next iteration
@@ -0,0 +1,44 @@
+use console_engine::pixel;
+use console_engine::Color;
+use console_engine::KeyCode;
+
+fn main() {
+    println!("Installing packages...");
+    // reserves 3 lines below the cursor with a target of 30 frames per second,
+    // the rest of the terminal (and its scrollback) is left untouched
+    let mut engine = console_engine::ConsoleEngine::init_inline(3, 30).unwrap();
+    // keep the final progress on the terminal once we're done
+    engine.set_keep_last_frame(true);
+
+    let packages = ["crossterm", "unicode-width", "unicode-segmentation", "rand"];
+    let mut progress = 0;
+    // main loop, be aware that you'll have to break it because ctrl+C is captured
+    while progress <= 100 {
+        engine.wait_frame(); // wait for next frame + capture inputs
+        engine.check_resize(); // follow the width of the terminal
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            // if the user presses 'q' :
+            break; // exits app
+        }
+        engine.clear_screen();
+
+        let current = packages[(progress * packages.len() / 101).min(packages.len() - 1)];
+        engine.print(0, 0, &format!("Downloading {}", current));
+
+        // draw the progress bar on the second line
+        let bar_width = engine.get_width() as i32 - 7;
+        let filled = bar_width * progress as i32 / 100;
+        engine.print(0, 1, "[");
+        if filled > 0 {
+            engine.line(1, 1, filled, 1, pixel::pxl_fg('=', Color::Green));
+        }
+        engine.print(bar_width + 1, 1, &format!("] {:>3}%", progress));
+        engine.print_fbg(0, 2, "press 'q' to abort", Color::DarkGrey, Color::Reset);
+
+        engine.draw(); // draw the screen
+
+        if engine.frame_count % 3 == 0 {
+            progress += 1;
+        }
+    }
+}
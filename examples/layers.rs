@@ -0,0 +1,84 @@
+use console_engine::compositor::{Compositor, Layer, Transparency};
+use console_engine::rect_style::BorderStyle;
+use console_engine::screen::Screen;
+use console_engine::{pixel, Color, KeyCode, MouseButton};
+
+fn main() {
+    // initializes a screen of 40x15 characters with a target of 30 frames per second
+    let mut engine = console_engine::ConsoleEngine::init(40, 15, 30).unwrap();
+
+    // the map is the bottom layer, filled with some terrain
+    let mut map = Screen::new_fill(40, 15, pixel::pxl_fbg('.', Color::DarkGreen, Color::Black));
+    map.fill_circle(28, 7, 5, pixel::pxl_fbg('~', Color::Cyan, Color::DarkBlue));
+    map.fill_rect(4, 3, 12, 8, pixel::pxl_fbg('^', Color::Grey, Color::Black));
+
+    // the hud is over the map, its spaces with a Color::Reset background are transparent
+    let mut hud = Screen::new(40, 1);
+    hud.print_fbg(1, 0, " HP: 100 ", Color::White, Color::DarkRed);
+    hud.print(30, 0, "q: quit");
+
+    // the popup can be dragged around, its '%' characters are transparent
+    let mut popup = Screen::new_fill(14, 5, pixel::pxl_bg(' ', Color::DarkGrey));
+    popup.rect_border(
+        0,
+        0,
+        13,
+        4,
+        BorderStyle::new_light().with_colors(Color::White, Color::DarkGrey),
+    );
+    popup.print_fbg(3, 2, "Drag me!", Color::White, Color::DarkGrey);
+    popup.set_pxl(13, 4, pixel::pxl('%'));
+
+    let mut compositor = Compositor::new();
+    compositor.add_layer("map", Layer::new(map));
+    compositor.add_layer(
+        "hud",
+        Layer::new(hud)
+            .with_z(2)
+            .with_transparency(Transparency::Background),
+    );
+    compositor.add_layer(
+        "popup",
+        Layer::new(popup)
+            .with_offset(13, 5)
+            .with_z(1)
+            .with_transparency(Transparency::Char('%')),
+    );
+
+    let mut dragging: Option<(i32, i32)> = None;
+    loop {
+        engine.wait_frame();
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            break;
+        }
+        // 'h' toggles the hud
+        if engine.is_key_pressed(KeyCode::Char('h')) {
+            let visible = compositor.get_layer("hud").unwrap().visible;
+            compositor.set_visible("hud", !visible);
+        }
+
+        let popup = compositor.get_layer_mut("popup").unwrap();
+        if let Some((x, y)) = engine.get_mouse_press(MouseButton::Left) {
+            let (x, y) = (x as i32, y as i32);
+            if x >= popup.x
+                && x < popup.x + popup.screen.get_width() as i32
+                && y >= popup.y
+                && y < popup.y + popup.screen.get_height() as i32
+            {
+                dragging = Some((x - popup.x, y - popup.y));
+            }
+        }
+        if let (Some((x, y)), Some((rel_x, rel_y))) =
+            (engine.get_mouse_held(MouseButton::Left), dragging)
+        {
+            popup.x = x as i32 - rel_x;
+            popup.y = y as i32 - rel_y;
+        }
+        if engine.get_mouse_released(MouseButton::Left).is_some() {
+            dragging = None;
+        }
+
+        engine.clear_screen();
+        engine.draw_compositor(&compositor);
+    }
+}
@@ -0,0 +1,79 @@
+use console_engine::layout::{Constraint, Layout, Margin};
+use console_engine::pixel;
+use console_engine::rect_style::BorderStyle;
+use console_engine::{Color, KeyCode};
+
+fn main() {
+    // initializes a screen filling the terminal with a target of 10 frame per second
+    let mut engine = console_engine::ConsoleEngine::init_fill(10).unwrap();
+
+    // header, body and footer
+    let mut rows = Layout::vertical(vec![
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ]);
+    // sidebar of a third of the screen (but at most 30 columns) and the main content
+    let mut columns = Layout::horizontal(vec![Constraint::Max(30), Constraint::Fill(1)]);
+    // the content is split into two panes of equal size
+    let mut panes = Layout::vertical(vec![Constraint::Percentage(50), Constraint::Fill(1)])
+        .padding(Margin::uniform(1));
+
+    // main loop, be aware that you'll have to break it because ctrl+C is captured
+    loop {
+        engine.wait_frame(); // wait for next frame + capture inputs
+        engine.check_resize(); // resize the terminal if its size has changed
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            break;
+        }
+        engine.clear_screen();
+
+        // the layouts are only recomputed when the size of the terminal changes
+        let regions = rows.split(engine.get_area());
+        let (header, body, footer) = (regions[0], regions[1], regions[2]);
+        let body_columns = columns.split(body);
+        let (sidebar, content) = (body_columns[0], body_columns[1]);
+
+        for (area, title) in [
+            (header, "Header"),
+            (sidebar, "Sidebar"),
+            (content, "Content"),
+        ] {
+            if area.is_empty() {
+                continue;
+            }
+            engine.rect_border(
+                area.x,
+                area.y,
+                area.right(),
+                area.bottom(),
+                BorderStyle::new_light(),
+            );
+            engine.print(area.x + 2, area.y, title);
+        }
+        for (i, pane) in panes.split(content).iter().enumerate() {
+            engine.fill_rect(
+                pane.x,
+                pane.y,
+                pane.right(),
+                pane.bottom(),
+                pixel::pxl_bg(
+                    ' ',
+                    if i == 0 {
+                        Color::DarkBlue
+                    } else {
+                        Color::DarkGreen
+                    },
+                ),
+            );
+            engine.print(pane.x, pane.y, &format!("{}x{}", pane.width, pane.height));
+        }
+        engine.print(
+            footer.x,
+            footer.y,
+            "Resize the terminal to see the layout adapt, press 'q' to quit",
+        );
+
+        engine.draw();
+    }
+}
@@ -18,9 +18,6 @@ fn random_color() -> (u8, u8, u8) {
 fn main() {
     // initializes a screen filling the terminal with a target of 120 frame per second
     let mut engine = console_engine::ConsoleEngine::init_fill(120).unwrap();
-    // We initalize a stopwatch for our FPS counter
-    let mut stopwatch = std::time::Instant::now();
-    let mut last_fps = 0;
     // main loop, be aware that you'll have to break it because ctrl+C is captured
     loop {
         engine.wait_frame(); // wait for next frame + capture inputs
@@ -51,19 +48,17 @@ fn main() {
             ),
         );
 
-        // we keep a small space to display FPS at the top-left corner
-        engine.print(0, 0, "         ");
-        engine.print(0, 0, format!("FPS: {}", last_fps).as_str());
+        // we keep a small space to display FPS, the time spent drawing and the bytes it wrote at the top-left corner
+        // the engine measures all of them for us
+        let stats = format!(
+            "FPS: {:.0} | draw: {}µs, {} bytes",
+            engine.get_fps(),
+            engine.get_draw_time().as_micros(),
+            engine.get_draw_size()
+        );
+        engine.print(0, 0, &" ".repeat(stats.len() + 2));
+        engine.print(0, 0, &stats);
 
         engine.draw(); // draw the screen
-
-        // if our stopwatch has reached 1 second,
-        // get the frame count and reset it for the next measure
-        // also reset the stopwatch for the next measure
-        if stopwatch.elapsed().as_millis() >= 1000 {
-            last_fps = engine.frame_count;
-            engine.frame_count = 0;
-            stopwatch = std::time::Instant::now();
-        }
     }
 }
@@ -0,0 +1,44 @@
+use console_engine::{events::Event, KeyCode};
+
+fn main() {
+    // initializes the engine in on demand mode:
+    // frames only happen when something changed, the engine sleeps the rest of the time
+    let mut engine = console_engine::ConsoleEngine::builder()
+        .size(40, 4)
+        .target_fps(60)
+        .on_demand(true)
+        .build()
+        .unwrap();
+    let mut counter = 0;
+
+    loop {
+        match engine.poll() {
+            // A redraw has been requested, or the terminal has been resized
+            Event::Frame => {
+                engine.clear_screen();
+                engine.print(0, 0, &format!("Counter: {}", counter));
+                engine.print(0, 1, &format!("Frames drawn: {}", engine.frame_count));
+                engine.print(0, 3, "+/- to change, f: toggle 5 fps, q: quit");
+                engine.draw();
+            }
+
+            Event::Key(keyevent) => {
+                match keyevent.code {
+                    KeyCode::Char('q') => break,
+                    KeyCode::Char('+') => counter += 1,
+                    KeyCode::Char('-') => counter -= 1,
+                    KeyCode::Char('f') => {
+                        // the target FPS can change at any time
+                        let fps = if engine.get_target_fps() == 60 { 5 } else { 60 };
+                        engine.set_target_fps(fps);
+                    }
+                    _ => continue,
+                }
+                // the state changed, we need a new frame
+                engine.request_redraw();
+            }
+
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use console_engine::record::{Recording, ReplaySpeed};
+use console_engine::{pixel, ConsoleEngine, KeyCode};
+
+/// Moves a character around with the arrow keys, until 'q' is pressed
+fn run(engine: &mut ConsoleEngine, title: &str) {
+    let mut x = 5;
+    let mut y = 2;
+    loop {
+        engine.wait_frame();
+        if engine.is_key_pressed(KeyCode::Char('q')) {
+            break;
+        }
+        if engine.is_key_pressed(KeyCode::Left) {
+            x -= 1;
+        }
+        if engine.is_key_pressed(KeyCode::Right) {
+            x += 1;
+        }
+        if engine.is_key_pressed(KeyCode::Up) {
+            y -= 1;
+        }
+        if engine.is_key_pressed(KeyCode::Down) {
+            y += 1;
+        }
+        engine.clear_screen();
+        engine.print(0, 0, title);
+        engine.set_pxl(x, y, pixel::pxl('@'));
+        engine.draw();
+    }
+}
+
+fn main() {
+    let path = std::env::temp_dir().join("console_engine-session.rec");
+    let mut engine = ConsoleEngine::init(40, 10, 30).unwrap();
+
+    // record a session
+    let file = std::fs::File::create(&path).unwrap();
+    engine
+        .start_recording(std::io::BufWriter::new(file))
+        .unwrap();
+    run(&mut engine, "Recording: move with arrows, 'q' to stop");
+    engine.stop_recording().unwrap();
+
+    // then play it back: the same keys are pressed in the same frames
+    let recording = Recording::load(&path).unwrap();
+    engine.start_replay(recording, ReplaySpeed::TargetFps);
+    run(&mut engine, "Replaying the session...");
+}
@@ -2,6 +2,12 @@ use console_engine::pixel;
 use console_engine::Color;
 use console_engine::ConsoleEngine;
 use console_engine::KeyCode;
+use std::time::Duration;
+
+/// timer moving the snake forward
+const MOVE: u32 = 0;
+/// timer switching the message displayed between two games
+const BLINK: u32 = 1;
 
 /// custom function for generating a random u32 bound into [0;max[
 fn random(max: u32) -> u32 {
@@ -20,6 +26,7 @@ enum Direction {
 /// The game logic fits in it
 struct Snake {
     playing: bool,
+    show_message: bool,
     bound_w: u32,
     bound_h: u32,
     direction: Direction,
@@ -37,6 +44,7 @@ impl Snake {
     pub fn init(game_width: u32, game_height: u32) -> Snake {
         Snake {
             playing: false,
+            show_message: true,
             bound_w: game_width,
             bound_h: game_height,
             direction: Direction::East,
@@ -116,6 +124,10 @@ impl Snake {
         }
     }
 
+    pub fn toggle_message(&mut self) {
+        self.show_message = !self.show_message;
+    }
+
     pub fn update_position(&mut self) {
         if self.playing {
             // calculates the delta_x and delta_y
@@ -208,7 +220,7 @@ impl Snake {
         } else {
             // blink a message, inviting the player to press space
             // and display controls on the other side
-            if engine.frame_count % 8 >= 4 {
+            if self.show_message {
                 engine.print_fbg(2, 1, "Press", Color::Yellow, Color::Black);
                 engine.print_fbg(2, 2, "Space", Color::Yellow, Color::Black);
                 engine.print_fbg(3, 3, "To", Color::Yellow, Color::Black);
@@ -227,12 +239,17 @@ impl Snake {
 }
 
 fn main() {
-    // initializes a screen filling the terminal of at least 10x10 of size with a target of 4 frame per second
-    let mut engine = console_engine::ConsoleEngine::init_fill_require(10, 10, 4).unwrap();
+    // initializes a screen filling the terminal of at least 10x10 of size with a target of 30 frame per second
+    let mut engine = console_engine::ConsoleEngine::init_fill_require(10, 10, 30).unwrap();
 
     // initialize game here, providing term size as boundaries
     let mut snake = Snake::init(engine.get_width(), engine.get_height());
 
+    // the game speed doesn't depend on the frame rate:
+    // the snake moves 4 times per second and the message blinks every second
+    engine.set_interval(Duration::from_millis(250), MOVE);
+    engine.set_interval(Duration::from_secs(1), BLINK);
+
     // main loop, be aware that you'll have to break it because ctrl+C is captured
     loop {
         engine.wait_frame(); // wait for next frame + capture inputs
@@ -246,7 +263,12 @@ fn main() {
 
         // run the game
         snake.input(&engine);
-        snake.update_position();
+        if engine.timer_fired(MOVE) {
+            snake.update_position();
+        }
+        if engine.timer_fired(BLINK) {
+            snake.toggle_message();
+        }
         // draw the game in engine's screen
         snake.draw(&mut engine);
 
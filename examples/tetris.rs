@@ -2,6 +2,12 @@ use console_engine::screen::Screen;
 use console_engine::Color;
 use console_engine::KeyCode;
 use console_engine::{pixel, ConsoleEngine};
+use std::time::Duration;
+
+/// timer making the piece fall
+const FALL: u32 = 0;
+/// timer ending the game after the game over screen
+const GAME_OVER: u32 = 1;
 
 /// This function returns a random tetromino
 fn random_tetromino() -> Tetromino {
@@ -438,7 +444,9 @@ fn main() {
     // constant values
     let start_pos_x = game_scr.get_width() as i32 / 2 - 1;
     let start_pos_y = 1;
-    let start_fall_counter = 10;
+    // pieces fall every second at first, then faster as the score increases
+    let start_fall_delay = Duration::from_millis(1000);
+    let min_fall_delay = Duration::from_millis(300);
 
     // initializes game
     let mut piece = random_tetromino();
@@ -446,8 +454,9 @@ fn main() {
     let mut piece_x = start_pos_x;
     let mut piece_y = start_pos_y;
     let mut piece_r = Orientation::Normal;
-    let mut fall_counter = start_fall_counter;
     let mut score: u32 = 0;
+    let mut fall_delay = start_fall_delay;
+    let mut fall_timer = engine.set_interval(fall_delay, FALL);
 
     // main loop, be aware that you'll have to break it because ctrl+C is captured
     loop {
@@ -477,13 +486,11 @@ fn main() {
             piece_x += 1;
         }
         // force the piece to drop
-        if engine.is_key_held(KeyCode::Char('2')) || engine.is_key_held(KeyCode::Down) {
-            fall_counter = 1;
-        }
+        let force_drop =
+            engine.is_key_held(KeyCode::Char('2')) || engine.is_key_held(KeyCode::Down);
 
-        fall_counter -= 1;
-        // drop the piece if the fall_counter hits zero
-        if fall_counter == 0 {
+        // drop the piece when the fall timer fires
+        if force_drop || engine.timer_fired(FALL) {
             // check if the piece can fall
             if piece_fits(&game_scr, &piece, &piece_r, piece_x, piece_y + 1) {
                 piece_y += 1
@@ -529,18 +536,25 @@ fn main() {
                     engine.rect(game_w + 3, 11, game_w + 13, 13, pixel::pxl('█'));
                     engine.print_fbg(game_w + 4, 12, "GAME OVER", Color::Black, Color::Grey);
 
-                    // wait 20 frames (=2 seconds) while still drawing the game
-                    for _ in 0..20 {
+                    // wait 2 seconds while still drawing the game
+                    engine.set_timeout(Duration::from_secs(2), GAME_OVER);
+                    while !engine.timer_fired(GAME_OVER) {
                         engine.wait_frame();
                         engine.draw();
                     }
                     break; // exit the game
                 }
             }
-            // reset the fall_counter to its start value.
-            // the more score the player have, the less we set the frame_counter
+            // the more score the player have, the shorter the fall delay
             // that makes the pieces drop faster
-            fall_counter = start_fall_counter - std::cmp::min(start_fall_counter - 3, score / 1000);
+            let delay = start_fall_delay
+                .saturating_sub(Duration::from_millis(100) * (score / 1000))
+                .max(min_fall_delay);
+            if delay != fall_delay {
+                fall_delay = delay;
+                engine.cancel_timer(fall_timer);
+                fall_timer = engine.set_interval(fall_delay, FALL);
+            }
         }
 
         // draw the game
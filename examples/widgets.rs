@@ -0,0 +1,78 @@
+use console_engine::{
+    events::Event,
+    widgets::{Gauge, ListView, ProgressBar, Sparkline, Table, Tabs, Widget},
+    ConsoleEngine, KeyCode,
+};
+use crossterm::event::KeyEvent;
+
+fn main() {
+    // Initialize the engine
+    let mut engine = ConsoleEngine::init(40, 12, 20).unwrap();
+
+    // Create the widgets, and tell the interactive ones where they are drawn so they can handle the mouse
+    let mut tabs = Tabs::new(40, vec!["List", "Table", "Progress"]);
+    tabs.set_active(true);
+
+    let mut list = ListView::new(38, 9, (1..=30).map(|i| format!("Sensor #{}", i)).collect());
+    list.set_active(true);
+    list.set_position(1, 2);
+
+    let mut table = Table::new(38, 9, vec!["Sensor", "Value", "Unit"], vec![20, 8, 8]);
+    for i in 1..=30 {
+        table.push_row(vec![
+            format!("Sensor #{}", i),
+            format!("{:.1}", (i as f32 * 1.7) % 40.0),
+            String::from("°C"),
+        ]);
+    }
+    table.set_active(true);
+    table.set_position(1, 2);
+
+    let mut progress = ProgressBar::new(38);
+    let mut gauge = Gauge::new(38, 3);
+    let mut sparkline = Sparkline::new(38, 3);
+
+    loop {
+        match engine.poll() {
+            // A frame has passed
+            Event::Frame => {
+                // update the progress widgets with dummy values
+                let ratio = (engine.frame_count % 200) as f32 / 200.0;
+                progress.set_progress(ratio);
+                gauge.set_ratio(1.0 - ratio);
+                sparkline.push(((engine.frame_count as f32 / 4.0).sin() * 50.0 + 50.0) as u64);
+
+                engine.clear_screen();
+                engine.print_screen(0, 0, tabs.draw());
+                match tabs.get_selected() {
+                    0 => engine.print_screen(1, 2, list.draw()),
+                    1 => engine.print_screen(1, 2, table.draw()),
+                    _ => {
+                        engine.print_screen(1, 2, progress.draw());
+                        engine.print_screen(1, 4, gauge.draw());
+                        engine.print_screen(1, 8, sparkline.draw());
+                    }
+                }
+                engine.print(0, 11, "Left/Right: tabs, Up/Down: select, Esc: quit");
+                engine.draw();
+            }
+
+            // exit with Escape
+            Event::Key(KeyEvent {
+                code: KeyCode::Esc, ..
+            }) => {
+                break;
+            }
+
+            // Let the widgets handle the other events
+            event => {
+                tabs.handle_event(event.clone());
+                match tabs.get_selected() {
+                    0 => list.handle_event(event),
+                    1 => table.handle_event(event),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
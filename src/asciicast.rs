@@ -0,0 +1,167 @@
+//! Recording of the drawn frames as an [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) file
+//!
+//! The output of each `draw` call is written with a timestamp, so the session can be played back
+//! with `asciinema play` or shared on asciinema.org without any screen capture tool.
+//! It also works with the [HeadlessBackend](../backend/struct.HeadlessBackend.html), to produce recordings in CI.
+//!
+//! see [ConsoleEngine::start_asciicast](../struct.ConsoleEngine.html#method.start_asciicast)
+
+use std::io::{self, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Writes an asciicast v2 stream: a header, then one event per line
+///
+/// usage:
+/// ```
+/// use console_engine::asciicast::AsciicastWriter;
+///
+/// let file = std::fs::File::create("demo.cast").unwrap();
+/// let mut cast = AsciicastWriter::new(file, 20, 5, Some("demo")).unwrap();
+/// cast.write_output(b"Hello, world!").unwrap();
+/// ```
+pub struct AsciicastWriter<W: Write> {
+    output: W,
+    start: Instant,
+}
+
+impl<W: Write> AsciicastWriter<W> {
+    /// Creates a writer for a terminal of the given size, writing the header right away
+    pub fn new(mut output: W, width: u32, height: u32, title: Option<&str>) -> io::Result<Self> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+        let mut header = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}",
+            width, height, timestamp
+        );
+        if let Some(title) = title {
+            header.push_str(&format!(", \"title\": {}", json_string(title)));
+        }
+        if let Ok(term) = std::env::var("TERM") {
+            header.push_str(&format!(", \"env\": {{\"TERM\": {}}}", json_string(&term)));
+        }
+        writeln!(output, "{}}}", header)?;
+        Ok(Self {
+            output,
+            start: Instant::now(),
+        })
+    }
+
+    /// Writes some data printed to the terminal
+    pub fn write_output(&mut self, data: &[u8]) -> io::Result<()> {
+        self.write_event("o", &String::from_utf8_lossy(data))
+    }
+
+    /// Records a resize of the terminal
+    pub fn write_resize(&mut self, width: u32, height: u32) -> io::Result<()> {
+        self.write_event("r", &format!("{}x{}", width, height))
+    }
+
+    /// Adds a marker, which players can jump to
+    pub fn write_marker(&mut self, label: &str) -> io::Result<()> {
+        self.write_event("m", label)
+    }
+
+    /// Flushes the underlying output
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+
+    /// Get the underlying output back
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_event(&mut self, code: &str, data: &str) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "[{:.6}, \"{}\", {}]",
+            self.start.elapsed().as_secs_f64(),
+            code,
+            json_string(data)
+        )
+    }
+}
+
+/// Formats a string as a JSON string literal
+fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for chr in string.chars() {
+        match chr {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            chr if (chr as u32) < 0x20 || chr == '\u{7f}' => {
+                json.push_str(&format!("\\u{:04x}", chr as u32))
+            }
+            chr => json.push(chr),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod test {
+    use super::{json_string, AsciicastWriter};
+    use crate::test::{engine, SharedOutput};
+
+    #[test]
+    fn json() {
+        assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(json_string("\x1b[1;1H\r\né"), "\"\\u001b[1;1H\\r\\né\"");
+    }
+
+    #[test]
+    fn events() {
+        let mut cast = AsciicastWriter::new(vec![], 20, 5, Some("test")).unwrap();
+        cast.write_output(b"\x1b[1;1Hhi").unwrap();
+        cast.write_resize(30, 6).unwrap();
+        cast.write_marker("end").unwrap();
+        let output = String::from_utf8(cast.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 20, \"height\": 5, "));
+        assert!(lines[0].contains("\"title\": \"test\""));
+        assert!(lines[1].starts_with('[') && lines[1].ends_with(", \"o\", \"\\u001b[1;1Hhi\"]"));
+        assert!(lines[2].ends_with(", \"r\", \"30x6\"]"));
+        assert!(lines[3].ends_with(", \"m\", \"end\"]"));
+    }
+
+    #[test]
+    fn engine_recording() {
+        let output = SharedOutput::default();
+        let mut engine = engine(5, 2);
+        engine.print(0, 0, "one");
+        engine.draw();
+        engine
+            .start_asciicast(output.clone(), Some("headless"))
+            .unwrap();
+        // the first frame of the recording is complete
+        engine.draw();
+        engine.resize(6, 2);
+        engine.print(0, 1, "two");
+        engine.draw();
+        engine.add_asciicast_marker("done");
+        engine.stop_asciicast().unwrap();
+        assert!(!engine.is_recording_asciicast());
+        engine.draw();
+
+        let cast = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = cast.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 5, \"height\": 2, "));
+        assert!(lines[1].ends_with(", \"o\", \"\\u001b[?25l\"]"));
+        assert!(lines[2].contains("one"));
+        assert!(lines[3].ends_with(", \"r\", \"6x2\"]"));
+        assert!(lines[4].contains("two"));
+        assert!(lines[5].ends_with(", \"m\", \"done\"]"));
+        // the terminal received the same frames
+        let terminal = String::from_utf8_lossy(engine.backend().output());
+        assert!(terminal.contains("two"));
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use crossterm::ErrorKind;
+
+use crate::color::ColorDepth;
+use crate::screen::Screen;
+
+use super::{Backend, EventReader};
+
+/// Number of drawn frames a HeadlessBackend keeps by default
+pub const DEFAULT_MAX_FRAMES: usize = 100;
+
+/// Backend that doesn't need any terminal
+///
+/// The terminal size is fixed (until a resize is scripted), input events come from a scripted queue,
+/// and every `draw` call stores a copy of the drawn screen that can be inspected afterwards.
+/// Only the last [DEFAULT_MAX_FRAMES] frames are kept, see [set_max_frames](#method.set_max_frames),
+/// and the raw output grows until it is taken with [take_output](#method.take_output).
+/// This allows running a ConsoleEngine in tests or in a CI without a pty.
+///
+/// usage:
+/// ```
+/// use console_engine::backend::HeadlessBackend;
+/// use console_engine::{ConsoleEngine, KeyCode};
+///
+/// let mut engine = ConsoleEngine::init_with_backend(HeadlessBackend::new(20, 10), 20, 10, 60).unwrap();
+/// engine.backend_mut().push_key(KeyCode::Char('q'));
+/// engine.wait_frame();
+/// assert!(engine.is_key_pressed(KeyCode::Char('q')));
+///
+/// engine.print(0, 0, "Hello, world!");
+/// engine.draw();
+/// assert_eq!(engine.backend().last_frame().unwrap().get_pxl(0, 0).unwrap().chr, 'H');
+/// ```
+pub struct HeadlessBackend {
+    size: Arc<Mutex<(u16, u16)>>,
+    events: Arc<Mutex<VecDeque<Event>>>,
+    frames: Vec<Screen>,
+    max_frames: Option<usize>,
+    output: Vec<u8>,
+    raw_mode: bool,
+    cursor: (u16, u16),
+    disconnected: Arc<AtomicBool>,
+    color_depth: ColorDepth,
+}
+
+impl HeadlessBackend {
+    /// Creates a new headless backend, emulating a terminal of the given size
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: Arc::new(Mutex::new((width, height))),
+            events: Arc::new(Mutex::new(VecDeque::new())),
+            frames: vec![],
+            max_frames: Some(DEFAULT_MAX_FRAMES),
+            output: vec![],
+            raw_mode: false,
+            cursor: (0, 0),
+            disconnected: Arc::new(AtomicBool::new(false)),
+            color_depth: ColorDepth::TrueColor,
+        }
+    }
+
+    /// Adds an event at the end of the input queue
+    pub fn push_event(&mut self, event: Event) {
+        self.events.lock().unwrap().push_back(event);
+    }
+
+    /// Adds a key press without modifiers at the end of the input queue
+    pub fn push_key(&mut self, code: KeyCode) {
+        self.push_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    /// Adds a key event at the end of the input queue
+    pub fn push_key_event(&mut self, event: KeyEvent) {
+        self.push_event(Event::Key(event));
+    }
+
+    /// Adds a mouse event at the end of the input queue
+    pub fn push_mouse(&mut self, event: MouseEvent) {
+        self.push_event(Event::Mouse(event));
+    }
+
+    /// Adds a resize event at the end of the input queue,
+    /// the size of the emulated terminal changes once the engine reads it
+    pub fn push_resize(&mut self, width: u16, height: u16) {
+        self.push_event(Event::Resize(width, height));
+    }
+
+    /// Number of events still waiting in the input queue
+    pub fn pending_events(&self) -> usize {
+        self.events.lock().unwrap().len()
+    }
+
+    /// Frames drawn so far, oldest first, up to the maximum number of frames kept
+    pub fn frames(&self) -> &[Screen] {
+        &self.frames
+    }
+
+    /// The last drawn frame, if any
+    pub fn last_frame(&self) -> Option<&Screen> {
+        self.frames.last()
+    }
+
+    /// Takes the frames drawn so far, leaving the frame list empty
+    pub fn take_frames(&mut self) -> Vec<Screen> {
+        std::mem::take(&mut self.frames)
+    }
+
+    /// Sets how many of the last drawn frames are kept ([DEFAULT_MAX_FRAMES] by default),
+    /// older frames being dropped. `None` keeps every frame, which grows without bound on long runs
+    pub fn set_max_frames(&mut self, max_frames: Option<usize>) {
+        self.max_frames = max_frames;
+        self.drop_old_frames();
+    }
+
+    fn drop_old_frames(&mut self) {
+        if let Some(max_frames) = self.max_frames {
+            let excess = self.frames.len().saturating_sub(max_frames);
+            self.frames.drain(..excess);
+        }
+    }
+
+    /// Raw bytes written by the engine so far (escape sequences included)
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// Takes the raw bytes written so far, leaving the output empty
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Sets the cursor position reported to the engine (`[0, 0]` by default)
+    pub fn set_cursor_position(&mut self, column: u16, row: u16) {
+        self.cursor = (column, row);
+    }
+
+    /// Sets the color depth reported to the engine ([TrueColor](../color/enum.ColorDepth.html#variant.TrueColor) by default),
+    /// the environment is never used to keep the output the same everywhere
+    pub fn set_color_depth(&mut self, color_depth: ColorDepth) {
+        self.color_depth = color_depth;
+    }
+
+    /// Emulates a terminal that has been closed (or reconnected):
+    /// while disconnected, writing, reading the size and polling the input fail with a broken pipe error
+    pub fn set_disconnected(&mut self, disconnected: bool) {
+        self.disconnected.store(disconnected, Ordering::Relaxed);
+    }
+
+    fn check_connected(&self) -> std::io::Result<()> {
+        if self.disconnected.load(Ordering::Relaxed) {
+            return Err(std::io::ErrorKind::BrokenPipe.into());
+        }
+        Ok(())
+    }
+
+    /// Checks whenever the engine enabled raw mode and didn't disable it yet
+    pub fn is_raw_mode_enabled(&self) -> bool {
+        self.raw_mode
+    }
+}
+
+impl Write for HeadlessBackend {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.check_connected()?;
+        self.output.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.check_connected()
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn size(&self) -> Result<(u16, u16), ErrorKind> {
+        self.check_connected()?;
+        Ok(*self.size.lock().unwrap())
+    }
+
+    fn enable_raw_mode(&mut self) -> Result<(), ErrorKind> {
+        self.raw_mode = true;
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> Result<(), ErrorKind> {
+        self.raw_mode = false;
+        Ok(())
+    }
+
+    fn poll_event(&mut self, timeout: Duration) -> Result<bool, ErrorKind> {
+        self.check_connected()?;
+        if self.events.lock().unwrap().is_empty() {
+            // behave like a terminal without input
+            std::thread::sleep(timeout);
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn read_event(&mut self) -> Result<Event, ErrorKind> {
+        pop_event(&self.events, &self.size)
+            .ok_or_else(|| ErrorKind::new(std::io::ErrorKind::Other, "No scripted event left"))
+    }
+
+    fn event_reader(&mut self) -> Option<EventReader> {
+        let events = self.events.clone();
+        let size = self.size.clone();
+        let disconnected = self.disconnected.clone();
+        Some(Box::new(move |timeout| {
+            if disconnected.load(Ordering::Relaxed) {
+                return Err(std::io::ErrorKind::BrokenPipe.into());
+            }
+            let event = pop_event(&events, &size);
+            if event.is_none() {
+                // events can be scripted at any time, so check again soon
+                std::thread::sleep(timeout.min(Duration::from_millis(1)));
+            }
+            Ok(event)
+        }))
+    }
+
+    fn cursor_position(&mut self) -> Result<(u16, u16), ErrorKind> {
+        Ok(self.cursor)
+    }
+
+    fn color_depth(&self) -> ColorDepth {
+        self.color_depth
+    }
+
+    fn present(&mut self, screen: &Screen) {
+        self.frames.push(screen.clone());
+        self.drop_old_frames();
+    }
+}
+
+/// Takes the next scripted event, a resize event changing the size of the emulated terminal as it is read
+fn pop_event(events: &Mutex<VecDeque<Event>>, size: &Mutex<(u16, u16)>) -> Option<Event> {
+    let event = events.lock().unwrap().pop_front();
+    if let Some(Event::Resize(width, height)) = event {
+        *size.lock().unwrap() = (width, height);
+    }
+    event
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use super::Backend;
+    use crate::pixel;
+    use crate::test::engine;
+
+    #[test]
+    fn frames() {
+        let mut engine = engine(10, 3);
+        assert!(engine.backend().is_raw_mode_enabled());
+        assert!(engine.backend().last_frame().is_none());
+
+        engine.print(0, 0, "Hello");
+        engine.draw();
+        engine.set_pxl(9, 2, pixel::pxl('#'));
+        engine.draw();
+
+        let frames = engine.backend().frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].get_pxl(4, 0).unwrap().chr, 'o');
+        assert_eq!(frames[0].get_pxl(9, 2).unwrap().chr, ' ');
+        assert_eq!(frames[1].get_pxl(9, 2).unwrap().chr, '#');
+        assert!(!engine.backend().output().is_empty());
+
+        assert_eq!(engine.backend_mut().take_frames().len(), 2);
+        assert!(engine.backend().frames().is_empty());
+        assert!(!engine.backend_mut().take_output().is_empty());
+        assert!(engine.backend().output().is_empty());
+
+        // only the last frames are kept
+        engine.backend_mut().set_max_frames(Some(2));
+        for x in 0..5 {
+            engine.set_pxl(x, 1, pixel::pxl('#'));
+            engine.draw();
+        }
+        let frames = engine.backend().frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].get_pxl(3, 1).unwrap().chr, '#');
+        assert_eq!(frames[0].get_pxl(4, 1).unwrap().chr, ' ');
+        assert_eq!(frames[1].get_pxl(4, 1).unwrap().chr, '#');
+    }
+
+    #[test]
+    fn scripted_input() {
+        let mut engine = engine(10, 3);
+        engine.backend_mut().push_key(KeyCode::Char('a'));
+        engine.backend_mut().push_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 3,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        engine.wait_frame();
+        assert_eq!(engine.backend().pending_events(), 0);
+        assert!(engine.is_key_pressed(KeyCode::Char('a')));
+        assert_eq!(engine.get_mouse_press(MouseButton::Left), Some((3, 2)));
+
+        engine.wait_frame();
+        assert!(!engine.is_key_pressed(KeyCode::Char('a')));
+        assert_eq!(engine.get_mouse_press(MouseButton::Left), None);
+    }
+
+    #[test]
+    fn resize() {
+        let mut engine = engine(10, 3);
+        engine.backend_mut().push_resize(20, 5);
+        // the terminal keeps its size until the resize event is read
+        assert_eq!(engine.backend().size().unwrap(), (10, 3));
+        engine.wait_frame();
+        assert_eq!(engine.get_resize(), Some((20, 5)));
+        engine.check_resize();
+        assert_eq!((engine.get_width(), engine.get_height()), (20, 5));
+        engine.draw();
+        assert_eq!(engine.backend().last_frame().unwrap().get_width(), 20);
+    }
+}
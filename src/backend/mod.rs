@@ -0,0 +1,301 @@
+//! Terminal backends used by ConsoleEngine
+
+use std::io::{Stdout, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::event::{self, Event};
+use crossterm::style::{self, Color};
+use crossterm::{queue, terminal, ErrorKind};
+
+use crate::color::ColorDepth;
+use crate::config::{EngineConfig, EngineSize};
+use crate::screen::Screen;
+
+mod headless;
+
+pub use headless::{HeadlessBackend, DEFAULT_MAX_FRAMES};
+
+/// Function reading input events from the input thread, see [Backend::event_reader]
+pub type EventReader = Box<dyn FnMut(Duration) -> Result<Option<Event>, ErrorKind> + Send>;
+
+/// Function restoring the terminal from the panic hook, see [Backend::panic_handler]
+pub type PanicHandler = Box<dyn Fn() + Send + Sync>;
+
+/// Function opening the output again from the panic hook, see [CrosstermBackend::panic_output]
+type PanicOutput = Arc<dyn Fn() -> std::io::Result<Box<dyn Write>> + Send + Sync>;
+
+/// Everything ConsoleEngine needs from a terminal
+///
+/// The engine writes its escape sequences through the [`Write`] implementation of the backend,
+/// and asks the backend for everything else that depends on the terminal (size, raw mode, input).
+///
+/// [`CrosstermBackend`] is the default backend, writing to stdout or to any other [`Write`].
+pub trait Backend: Write {
+    /// Get the size of the terminal, as columns and rows
+    fn size(&self) -> Result<(u16, u16), ErrorKind>;
+
+    /// Enable raw mode, called when the engine starts
+    fn enable_raw_mode(&mut self) -> Result<(), ErrorKind>;
+
+    /// Disable raw mode, called when the engine stops
+    fn disable_raw_mode(&mut self) -> Result<(), ErrorKind>;
+
+    /// Checks if an input event is available within the given timeout
+    fn poll_event(&mut self, timeout: Duration) -> Result<bool, ErrorKind>;
+
+    /// Reads the next input event.
+    /// Only called after [poll_event](#tymethod.poll_event) returned `true`
+    fn read_event(&mut self) -> Result<Event, ErrorKind>;
+
+    /// Creates a function reading input events from another thread, used by the input thread of the engine.
+    /// The function waits up to the given timeout for an event.
+    ///
+    /// Returning `None` means the input can only be read from the engine's thread,
+    /// through [poll_event](#tymethod.poll_event) and [read_event](#tymethod.read_event)
+    fn event_reader(&mut self) -> Option<EventReader> {
+        None
+    }
+
+    /// Get the position of the cursor, as column and row.
+    /// Used by inline engines to know where to draw.
+    ///
+    /// Backends that can't query the cursor return `[0, 0]`
+    fn cursor_position(&mut self) -> Result<(u16, u16), ErrorKind> {
+        Ok((0, 0))
+    }
+
+    /// Get the colors the terminal is able to display.
+    /// The colors of each frame are converted to the closest supported ones
+    ///
+    /// Detected from the environment by default, see [ColorDepth::detect]
+    fn color_depth(&self) -> ColorDepth {
+        ColorDepth::detect()
+    }
+
+    /// Called at the end of each `draw` call with the screen that has just been drawn
+    fn present(&mut self, _screen: &Screen) {}
+
+    /// Function called from the panic hook to restore the terminal, since the engine itself can't be reached from there.
+    /// It should only undo what the engine enabled with the given configuration.
+    ///
+    /// Returning `None` means there is nothing to restore, so no panic hook will be installed
+    fn panic_handler(&self, _config: &EngineConfig) -> Option<PanicHandler> {
+        None
+    }
+}
+
+/// Backend relying on crossterm for the terminal handling
+///
+/// The output can be any [`Write`] (a file, a pipe, another tty, ...), and defaults to stdout.
+/// Size, raw mode and input are always taken from the terminal the program runs in.
+///
+/// usage:
+/// ```
+/// use console_engine::backend::CrosstermBackend;
+/// use console_engine::ConsoleEngine;
+///
+/// let open_tty = || std::fs::OpenOptions::new().write(true).open("/dev/pts/3");
+/// let backend = CrosstermBackend::new(open_tty().unwrap()).panic_output(open_tty);
+/// let mut engine = ConsoleEngine::init_with_backend(backend, 20, 10, 30).unwrap();
+/// ```
+pub struct CrosstermBackend<W: Write = Stdout> {
+    output: W,
+    panic_output: Option<PanicOutput>,
+}
+
+impl<W: Write> CrosstermBackend<W> {
+    /// Creates a new backend writing into the given output
+    ///
+    /// Only the output is taken from the given writer: the size of the screen, raw mode and the input
+    /// still come from the terminal the program runs in. Writing to another tty therefore needs
+    /// a screen size that fits it, and the input has to be typed in the terminal of the program.
+    ///
+    /// The panic hook can't reach the output, it only disables raw mode
+    /// unless [panic_output](#method.panic_output) is also given
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            panic_output: None,
+        }
+    }
+
+    /// Gives the panic hook a way to open the output again,
+    /// so it can also leave the alternate screen, show the cursor and stop the mouse capture on it
+    pub fn panic_output<F, O>(mut self, open: F) -> Self
+    where
+        F: Fn() -> std::io::Result<O> + Send + Sync + 'static,
+        O: Write + 'static,
+    {
+        self.panic_output = Some(Arc::new(move || {
+            open().map(|output| Box::new(output) as Box<dyn Write>)
+        }));
+        self
+    }
+
+    /// Get a reference to the output
+    pub fn get_ref(&self) -> &W {
+        &self.output
+    }
+
+    /// Get a mutable reference to the output
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.output
+    }
+}
+
+impl Default for CrosstermBackend<Stdout> {
+    fn default() -> Self {
+        Self::new(std::io::stdout()).panic_output(|| Ok(std::io::stdout()))
+    }
+}
+
+impl<W: Write> Write for CrosstermBackend<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Backend for CrosstermBackend<W> {
+    fn size(&self) -> Result<(u16, u16), ErrorKind> {
+        terminal::size()
+    }
+
+    fn enable_raw_mode(&mut self) -> Result<(), ErrorKind> {
+        terminal::enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> Result<(), ErrorKind> {
+        terminal::disable_raw_mode()
+    }
+
+    fn poll_event(&mut self, timeout: Duration) -> Result<bool, ErrorKind> {
+        event::poll(timeout)
+    }
+
+    fn read_event(&mut self) -> Result<Event, ErrorKind> {
+        event::read()
+    }
+
+    fn event_reader(&mut self) -> Option<EventReader> {
+        // crossterm reads the terminal input from any thread
+        Some(Box::new(|timeout| {
+            if event::poll(timeout)? {
+                event::read().map(Some)
+            } else {
+                Ok(None)
+            }
+        }))
+    }
+
+    fn cursor_position(&mut self) -> Result<(u16, u16), ErrorKind> {
+        // the cursor must be where everything written so far left it
+        self.output.flush()?;
+        crossterm::cursor::position()
+    }
+
+    fn panic_handler(&self, config: &EngineConfig) -> Option<PanicHandler> {
+        if !config.raw_mode && self.panic_output.is_none() {
+            return None;
+        }
+        let config = *config;
+        let panic_output = self.panic_output.clone();
+        Some(Box::new(move || {
+            // escape sequences are only sent when the output can be reached,
+            // rather than to a terminal the engine didn't draw on
+            if let Some(Ok(mut output)) = panic_output.as_ref().map(|open| open()) {
+                restore_output(&mut output, &config);
+            }
+            // raw mode belongs to the terminal the program runs in, whatever the output is
+            if config.raw_mode {
+                terminal::disable_raw_mode().ok();
+            }
+        }))
+    }
+}
+
+/// Restores the output when a panic occurs.
+/// Similar to ConsoleEngine's end function, but without the engine instance,
+/// undoing what the engine enabled on the output with its configuration.
+fn restore_output(output: &mut impl Write, config: &EngineConfig) {
+    if config.bracketed_paste {
+        queue!(output, crossterm::event::DisableBracketedPaste).ok();
+    }
+    if config.focus_reporting {
+        queue!(output, crossterm::event::DisableFocusChange).ok();
+    }
+    if config.mouse_capture {
+        queue!(output, crossterm::event::DisableMouseCapture).ok();
+    }
+    if config.hide_cursor {
+        queue!(output, crossterm::cursor::Show).ok();
+    }
+    queue!(
+        output,
+        style::SetAttribute(style::Attribute::Reset),
+        style::SetBackgroundColor(Color::Reset),
+        style::SetForegroundColor(Color::Reset)
+    )
+    .ok();
+    if matches!(config.size, EngineSize::Inline { .. }) {
+        // the panic message is printed below the frame
+        queue!(output, style::Print("\r\n")).ok();
+    } else if config.alternate_screen {
+        queue!(output, terminal::LeaveAlternateScreen).ok();
+    }
+    output.flush().ok();
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use super::{Backend, CrosstermBackend};
+    use crate::config::EngineConfig;
+
+    #[test]
+    fn panic_handler() {
+        let config = EngineConfig::default();
+        assert!(CrosstermBackend::default().panic_handler(&config).is_some());
+        // raw mode is always disabled, even if the output can't be reached
+        assert!(CrosstermBackend::new(Vec::new())
+            .panic_handler(&config)
+            .is_some());
+        let config = EngineConfig {
+            raw_mode: false,
+            ..Default::default()
+        };
+        assert!(CrosstermBackend::new(Vec::new())
+            .panic_handler(&config)
+            .is_none());
+
+        // the output is restored through the one given to the panic hook
+        let restored = Arc::new(Mutex::new(Vec::new()));
+        let output = restored.clone();
+        let handler = CrosstermBackend::new(Vec::new())
+            .panic_output(move || Ok(SharedOutput(output.clone())))
+            .panic_handler(&config)
+            .unwrap();
+        handler();
+        let restored = String::from_utf8(restored.lock().unwrap().clone()).unwrap();
+        assert!(restored.contains("\x1b[?25h"));
+        assert!(restored.ends_with("\x1b[?1049l"));
+    }
+
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! High resolution drawing surface
+//!
+//! A [Canvas] covers an area of terminal cells but is drawn dot by dot, each cell being split into
+//! 2x4 dots with Braille patterns or into 1x2 dots with half blocks (`▀▄`).
+//! It supports the same primitives as a [Screen], and is then blitted onto a Screen.
+//!
+//! see example `canvas`
+
+use crossterm::style::Color;
+
+use crate::pixel;
+use crate::screen::Screen;
+
+/// How the cells of a Canvas are split
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CanvasMode {
+    /// Each cell is split into 2x4 dots using Braille patterns.
+    /// A cell can only display one color, the most used color of its dots
+    Braille,
+    /// Each cell is split into 1x2 dots using half blocks.
+    /// The upper and lower halves of a cell keep their own color
+    HalfBlock,
+}
+
+impl CanvasMode {
+    /// Number of dots per cell, horizontally and vertically
+    pub fn cell_size(&self) -> (u32, u32) {
+        match self {
+            CanvasMode::Braille => (2, 4),
+            CanvasMode::HalfBlock => (1, 2),
+        }
+    }
+}
+
+/// Bit of each dot of a Braille cell, indexed by `[y][x]`
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Sub-cell drawing surface
+///
+/// Coordinates are given in dots, the canvas being `width * 2` by `height * 4` dots in Braille mode
+/// and `width` by `height * 2` dots in half block mode (where width and height are in cells).
+/// Out of bounds dots are ignored.
+///
+/// Cells without any dot are left untouched when blitting, so a canvas can be drawn over existing content.
+///
+/// usage:
+/// ```
+/// use console_engine::canvas::{Canvas, CanvasMode};
+/// use console_engine::Color;
+///
+/// let mut canvas = Canvas::new(20, 5, CanvasMode::Braille);
+/// canvas.line(0, 19, 39, 0, Color::Green);
+/// canvas.circle(20, 10, 8, Color::Yellow);
+///
+/// engine.blit_canvas(0, 0, &canvas);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    mode: CanvasMode,
+    dots: Vec<Option<Color>>,
+}
+
+impl Canvas {
+    /// Creates a new empty canvas covering the given amount of cells
+    pub fn new(width: u32, height: u32, mode: CanvasMode) -> Canvas {
+        let (cell_w, cell_h) = mode.cell_size();
+        Canvas {
+            width,
+            height,
+            mode,
+            dots: vec![None; (width * cell_w * height * cell_h) as usize],
+        }
+    }
+
+    /// Get the canvas width in cells
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the canvas height in cells
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the canvas mode
+    pub fn get_mode(&self) -> CanvasMode {
+        self.mode
+    }
+
+    /// Get the canvas width in dots
+    pub fn get_dot_width(&self) -> u32 {
+        self.width * self.mode.cell_size().0
+    }
+
+    /// Get the canvas height in dots
+    pub fn get_dot_height(&self) -> u32 {
+        self.height * self.mode.cell_size().1
+    }
+
+    /// Removes every dot of the canvas
+    pub fn clear(&mut self) {
+        self.dots.fill(None);
+    }
+
+    /// Changes the size of the canvas (in cells), keeping the dots that still fit
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let mut resized = Canvas::new(width, height, self.mode);
+        for y in 0..self.get_dot_height().min(resized.get_dot_height()) as i32 {
+            for x in 0..self.get_dot_width().min(resized.get_dot_width()) as i32 {
+                if let Some(color) = self.get_dot(x, y) {
+                    resized.set_dot(x, y, color);
+                }
+            }
+        }
+        *self = resized;
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && y >= 0 && x < self.get_dot_width() as i32 && y < self.get_dot_height() as i32 {
+            Some((y as u32 * self.get_dot_width() + x as u32) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Sets a dot at the given coordinates
+    pub fn set_dot(&mut self, x: i32, y: i32, color: Color) {
+        if let Some(index) = self.index(x, y) {
+            self.dots[index] = Some(color);
+        }
+    }
+
+    /// Removes the dot at the given coordinates
+    pub fn unset_dot(&mut self, x: i32, y: i32) {
+        if let Some(index) = self.index(x, y) {
+            self.dots[index] = None;
+        }
+    }
+
+    /// Get the color of the dot at the given coordinates, if it's set
+    pub fn get_dot(&self, x: i32, y: i32) -> Option<Color> {
+        self.index(x, y).and_then(|index| self.dots[index])
+    }
+
+    /// Draws a line between two sets of coordinates
+    pub fn line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        crate::shapes::line(start_x, start_y, end_x, end_y, &mut |x, y| {
+            self.set_dot(x, y, color)
+        });
+    }
+
+    /// Draws a rectangle between two sets of coordinates
+    pub fn rect(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        self.line(start_x, start_y, end_x, start_y, color); // top
+        self.line(end_x, start_y, end_x, end_y, color); // right
+        self.line(end_x, end_y, start_x, end_y, color); // bottom
+        self.line(start_x, end_y, start_x, start_y, color); // left
+    }
+
+    /// Fill a rectangle between two sets of coordinates
+    pub fn fill_rect(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        for y in start_y.min(end_y)..=start_y.max(end_y) {
+            self.line(start_x, y, end_x, y, color);
+        }
+    }
+
+    /// Draws a circle at an x and y position with a radius
+    pub fn circle(&mut self, x: i32, y: i32, radius: u32, color: Color) {
+        crate::shapes::circle(x, y, radius, &mut |x, y| self.set_dot(x, y, color));
+    }
+
+    /// Fill a circle at an x and y position with a radius
+    pub fn fill_circle(&mut self, x: i32, y: i32, radius: u32, color: Color) {
+        crate::shapes::fill_circle(x, y, radius, &mut |x, y| self.set_dot(x, y, color));
+    }
+
+    /// Draws a triangle using three sets of coordinates
+    #[allow(clippy::too_many_arguments)]
+    pub fn triangle(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, color: Color) {
+        self.line(x1, y1, x2, y2, color);
+        self.line(x2, y2, x3, y3, color);
+        self.line(x3, y3, x1, y1, color);
+    }
+
+    /// Fill a triangle using three sets of coordinates
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_triangle(
+        &mut self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        x3: i32,
+        y3: i32,
+        color: Color,
+    ) {
+        self.triangle(x1, y1, x2, y2, x3, y3, color);
+        let (width, height) = (self.get_dot_width(), self.get_dot_height());
+        crate::shapes::fill_triangle(x1, y1, x2, y2, x3, y3, width, height, &mut |x, y| {
+            self.set_dot(x, y, color)
+        });
+    }
+
+    /// Draws the canvas onto a screen at the given position
+    ///
+    /// Cells without any dot are skipped, and the drawn cells keep the background of the screen
+    /// (except for half blocks having two different colors, which use the background for the lower half)
+    pub fn blit(&self, screen: &mut Screen, x: i32, y: i32) {
+        for cell_y in 0..self.height as i32 {
+            for cell_x in 0..self.width as i32 {
+                let (target_x, target_y) = (x + cell_x, y + cell_y);
+                // out of bounds cells are ignored
+                let under = match screen.get_pxl(target_x, target_y) {
+                    Ok(under) => under,
+                    Err(_) => continue,
+                };
+                let cell = match self.mode {
+                    CanvasMode::Braille => self.braille_cell(cell_x, cell_y, under.bg),
+                    CanvasMode::HalfBlock => self.half_block_cell(cell_x, cell_y, under.bg),
+                };
+                if let Some(cell) = cell {
+                    screen.set_pxl(target_x, target_y, cell);
+                }
+            }
+        }
+    }
+
+    /// Creates a new screen of the size of the canvas and draws the canvas into it
+    pub fn to_screen(&self) -> Screen {
+        let mut screen = Screen::new(self.width, self.height);
+        self.blit(&mut screen, 0, 0);
+        screen
+    }
+
+    fn braille_cell(&self, cell_x: i32, cell_y: i32, bg: Color) -> Option<pixel::Pixel> {
+        let mut bits = 0;
+        // colors of the cell along with their dot count, in order of appearance
+        let mut colors: Vec<(Color, u32)> = vec![];
+        for (dy, row) in BRAILLE_DOTS.iter().enumerate() {
+            for (dx, bit) in row.iter().enumerate() {
+                if let Some(color) = self.get_dot(cell_x * 2 + dx as i32, cell_y * 4 + dy as i32) {
+                    bits |= bit;
+                    match colors.iter_mut().find(|(c, _)| *c == color) {
+                        Some((_, count)) => *count += 1,
+                        None => colors.push((color, 1)),
+                    }
+                }
+            }
+        }
+        if bits == 0 {
+            return None;
+        }
+        let mut fg = colors[0];
+        for color in colors.iter().skip(1) {
+            if color.1 > fg.1 {
+                fg = *color;
+            }
+        }
+        // unwrap here because every value between U+2800 and U+28FF is a Braille pattern
+        let chr = char::from_u32(0x2800 + bits).unwrap();
+        Some(pixel::pxl_fbg(chr, fg.0, bg))
+    }
+
+    fn half_block_cell(&self, cell_x: i32, cell_y: i32, bg: Color) -> Option<pixel::Pixel> {
+        let upper = self.get_dot(cell_x, cell_y * 2);
+        let lower = self.get_dot(cell_x, cell_y * 2 + 1);
+        match (upper, lower) {
+            (None, None) => None,
+            (Some(upper), None) => Some(pixel::pxl_fbg('▀', upper, bg)),
+            (None, Some(lower)) => Some(pixel::pxl_fbg('▄', lower, bg)),
+            (Some(upper), Some(lower)) if upper == lower => Some(pixel::pxl_fbg('█', upper, bg)),
+            (Some(upper), Some(lower)) => Some(pixel::pxl_fbg('▀', upper, lower)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::Color;
+
+    use super::{Canvas, CanvasMode};
+    use crate::{pixel, screen::Screen};
+
+    #[test]
+    fn braille() {
+        let mut canvas = Canvas::new(2, 1, CanvasMode::Braille);
+        assert_eq!((canvas.get_dot_width(), canvas.get_dot_height()), (4, 4));
+        canvas.line(0, 0, 0, 3, Color::Red);
+        canvas.set_dot(3, 3, Color::Blue);
+        canvas.set_dot(3, 0, Color::Green);
+        canvas.set_dot(3, 1, Color::Blue);
+        // out of bounds
+        canvas.set_dot(4, 0, Color::Red);
+
+        let screen = canvas.to_screen();
+        assert_eq!(screen.to_text(), "⡇⢘");
+        assert_eq!(screen.get_pxl(0, 0).unwrap().fg, Color::Red);
+        // the most used color wins
+        assert_eq!(screen.get_pxl(1, 0).unwrap().fg, Color::Blue);
+
+        canvas.clear();
+        assert_eq!(canvas.to_screen().to_text(), "  ");
+    }
+
+    #[test]
+    fn half_block() {
+        let mut canvas = Canvas::new(4, 1, CanvasMode::HalfBlock);
+        canvas.set_dot(0, 0, Color::Red);
+        canvas.set_dot(1, 1, Color::Red);
+        canvas.fill_rect(2, 0, 2, 1, Color::Green);
+        canvas.set_dot(3, 0, Color::Yellow);
+        canvas.set_dot(3, 1, Color::Blue);
+
+        let mut screen = Screen::new_fill(5, 1, pixel::pxl_bg('.', Color::Magenta));
+        canvas.blit(&mut screen, 1, 0);
+        assert_eq!(screen.to_text(), ".▀▄█▀");
+        assert_eq!(
+            screen.get_pxl(1, 0).unwrap(),
+            pixel::pxl_fbg('▀', Color::Red, Color::Magenta)
+        );
+        assert_eq!(
+            screen.get_pxl(4, 0).unwrap(),
+            pixel::pxl_fbg('▀', Color::Yellow, Color::Blue)
+        );
+    }
+
+    #[test]
+    fn shapes() {
+        let mut canvas = Canvas::new(3, 3, CanvasMode::HalfBlock);
+        canvas.fill_circle(1, 2, 1, Color::White);
+        assert_eq!(canvas.to_screen().to_text(), " ▄ \n▀█▀\n   ");
+
+        let mut canvas = Canvas::new(2, 1, CanvasMode::Braille);
+        canvas.rect(0, 0, 3, 3, Color::White);
+        assert_eq!(canvas.to_screen().to_text(), "⣏⣹");
+
+        canvas.resize(1, 1);
+        assert_eq!(canvas.to_screen().to_text(), "⣏");
+    }
+}
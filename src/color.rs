@@ -0,0 +1,609 @@
+//! Colors: terminal support, conversions, blending and gradients
+//!
+//! Terminals don't all support the same colors: modern terminals display any [Color::Rgb],
+//! others are limited to a palette of 256 or 16 colors, and some serial consoles can't display colors at all.
+//! The engine detects the [ColorDepth] of the terminal and converts the colors of each frame to the closest supported ones.
+//!
+//! The other functions compute colors: [HSL](fn.hsl.html) and [HSV](fn.hsv.html) conversions,
+//! [interpolation](fn.lerp.html) between two colors, and multi-stop [gradients](struct.Gradient.html).
+
+use crossterm::style::Color;
+
+/// Colors a terminal is able to display
+///
+/// usage:
+/// ```
+/// use console_engine::color::ColorDepth;
+/// use console_engine::Color;
+///
+/// let depth = ColorDepth::detect();
+/// // the closest color this terminal can display
+/// let orange = depth.map(Color::Rgb { r: 255, g: 128, b: 0 });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// No color at all, only the attributes are kept
+    NoColor,
+    /// The 16 base ANSI colors
+    Ansi16,
+    /// The 256 colors palette: the 16 base colors, a 6x6x6 color cube and 24 shades of grey
+    Ansi256,
+    /// Any RGB color
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detects the color depth of the terminal from the environment
+    ///
+    /// - `NO_COLOR` set to a non empty value disables colors, see [no-color.org](https://no-color.org)
+    /// - `COLORTERM` set to `truecolor` or `24bit` enables RGB colors
+    /// - otherwise `TERM` gives the palette: `dumb` and vt terminals have no colors,
+    ///   `*-256color` terminals have 256 colors, `*-direct` terminals have RGB colors,
+    ///   and every other terminal is assumed to have the 16 base colors
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        Self::from_env(
+            var("NO_COLOR").as_deref(),
+            var("COLORTERM").as_deref(),
+            var("TERM").as_deref(),
+        )
+    }
+
+    /// Get the color depth matching the given values of `NO_COLOR`, `COLORTERM` and `TERM`,
+    /// see [detect](#method.detect)
+    pub fn from_env(no_color: Option<&str>, colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if no_color.is_some_and(|value| !value.is_empty()) {
+            return ColorDepth::NoColor;
+        }
+        let term = term.unwrap_or_default();
+        if term == "dumb" || term.starts_with("vt") {
+            return ColorDepth::NoColor;
+        }
+        if matches!(colorterm, Some("truecolor") | Some("24bit")) || term.ends_with("-direct") {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else if term.is_empty() && cfg!(windows) {
+            // the Windows console doesn't set TERM, and supports RGB colors since Windows 10
+            ColorDepth::TrueColor
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    /// Converts a color to the closest color supported by this depth
+    ///
+    /// With [NoColor](#variant.NoColor), every color becomes [Color::Reset]
+    pub fn map(self, color: Color) -> Color {
+        match (self, color) {
+            (_, Color::Reset) | (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::NoColor, _) => Color::Reset,
+            (ColorDepth::Ansi256, Color::Rgb { r, g, b }) => {
+                Color::AnsiValue(rgb_to_ansi256(r, g, b))
+            }
+            (ColorDepth::Ansi256, _) => color,
+            (ColorDepth::Ansi16, Color::Rgb { r, g, b }) => rgb_to_ansi16(r, g, b),
+            (ColorDepth::Ansi16, Color::AnsiValue(value)) => match value {
+                0..=15 => ANSI16[value as usize].0,
+                _ => {
+                    let (r, g, b) = ansi256_to_rgb(value);
+                    rgb_to_ansi16(r, g, b)
+                }
+            },
+            (ColorDepth::Ansi16, _) => color,
+        }
+    }
+}
+
+/// Get the RGB value of a color, using the xterm values for the 16 base colors.
+/// [Color::Reset] has no RGB value, since it depends on the terminal
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(value) => Some(ansi256_to_rgb(value)),
+        color => ANSI16
+            .iter()
+            .find(|(base, _)| *base == color)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+/// Creates a color from its hue (in degrees), saturation and lightness (between 0 and 1)
+///
+/// usage:
+/// ```
+/// use console_engine::color;
+///
+/// // a pastel orange
+/// let orange = color::hsl(30.0, 1.0, 0.75);
+/// ```
+pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    from_hue(hue, chroma, lightness - chroma / 2.0)
+}
+
+/// Get the hue (in degrees), saturation and lightness (between 0 and 1) of a color,
+/// `None` for [Color::Reset]
+pub fn to_hsl(color: Color) -> Option<(f32, f32, f32)> {
+    let (hue, max, min) = to_hue(color)?;
+    let lightness = (max + min) / 2.0;
+    let saturation = if max == min {
+        0.0
+    } else {
+        (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+    };
+    Some((hue, saturation, lightness))
+}
+
+/// Creates a color from its hue (in degrees), saturation and value (between 0 and 1)
+///
+/// usage:
+/// ```
+/// use console_engine::color;
+///
+/// // every hue of the color wheel
+/// let rainbow: Vec<_> = (0..12).map(|i| color::hsv(i as f32 * 30.0, 1.0, 1.0)).collect();
+/// ```
+pub fn hsv(hue: f32, saturation: f32, value: f32) -> Color {
+    let value = value.clamp(0.0, 1.0);
+    let chroma = value * saturation.clamp(0.0, 1.0);
+    from_hue(hue, chroma, value - chroma)
+}
+
+/// Get the hue (in degrees), saturation and value (between 0 and 1) of a color,
+/// `None` for [Color::Reset]
+pub fn to_hsv(color: Color) -> Option<(f32, f32, f32)> {
+    let (hue, max, min) = to_hue(color)?;
+    let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+    Some((hue, saturation, max))
+}
+
+/// Builds a color from a hue, a chroma and the amount added to each channel
+fn from_hue(hue: f32, chroma: f32, offset: f32) -> Color {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    let channel = |value: f32| ((value + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Color::Rgb {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+    }
+}
+
+/// Get the hue of a color, with its highest and lowest channels between 0 and 1
+fn to_hue(color: Color) -> Option<(f32, f32, f32)> {
+    let (r, g, b) = to_rgb(color)?;
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    Some((hue, max, min))
+}
+
+/// Interpolates between two colors, `amount` going from 0 (`from`) to 1 (`to`)
+///
+/// [Color::Reset] can't be interpolated, so it is kept until the middle
+///
+/// usage:
+/// ```
+/// use console_engine::{color, Color};
+///
+/// let orange = color::lerp(Color::Red, Color::Yellow, 0.5);
+/// ```
+pub fn lerp(from: Color, to: Color, amount: f32) -> Color {
+    let amount = if amount.is_nan() {
+        0.0
+    } else {
+        amount.clamp(0.0, 1.0)
+    };
+    match (to_rgb(from), to_rgb(to)) {
+        (Some(from), Some(to)) => {
+            let channel =
+                |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount).round() as u8;
+            Color::Rgb {
+                r: channel(from.0, to.0),
+                g: channel(from.1, to.1),
+                b: channel(from.2, to.2),
+            }
+        }
+        _ if amount < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// Draws a color over a background with the given opacity, from 0 (transparent) to 1 (opaque)
+///
+/// usage:
+/// ```
+/// use console_engine::{color, Color};
+///
+/// // a red tint over a blue background
+/// let tinted = color::blend(Color::Blue, Color::Red, 0.25);
+/// ```
+pub fn blend(background: Color, color: Color, alpha: f32) -> Color {
+    lerp(background, color, alpha)
+}
+
+/// Colors placed along a line, giving the color of any position between 0 and 1
+///
+/// usage:
+/// ```
+/// use console_engine::color::Gradient;
+/// use console_engine::Color;
+///
+/// // a health bar going from red to green
+/// let gradient = Gradient::new(&[Color::Red, Color::Yellow, Color::Green]);
+/// let color = gradient.sample(health as f32 / max_health as f32);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    /// Creates a gradient going through the given colors, evenly spaced.
+    /// There must be at least one color
+    pub fn new(colors: &[Color]) -> Self {
+        assert!(!colors.is_empty(), "A gradient needs at least one color.");
+        let last = (colors.len() - 1).max(1) as f32;
+        Self {
+            stops: colors
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i as f32 / last, *color))
+                .collect(),
+        }
+    }
+
+    /// Creates a gradient from colors at the given positions, between 0 and 1.
+    /// There must be at least one color
+    pub fn from_stops(stops: &[(f32, Color)]) -> Self {
+        assert!(!stops.is_empty(), "A gradient needs at least one color.");
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    /// Get the colors of the gradient, with their positions
+    pub fn get_stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// Get the color at the given position, between 0 and 1
+    pub fn sample(&self, position: f32) -> Color {
+        let first = self.stops[0];
+        if position.is_nan() || position <= first.0 {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let ((start, from), (end, to)) = (pair[0], pair[1]);
+            if position <= end {
+                let amount = if end > start {
+                    (position - start) / (end - start)
+                } else {
+                    1.0
+                };
+                return lerp(from, to, amount);
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+
+    /// Get the given number of colors, evenly spaced along the gradient
+    pub fn colors(&self, count: usize) -> Vec<Color> {
+        let last = (count.max(2) - 1) as f32;
+        (0..count).map(|i| self.sample(i as f32 / last)).collect()
+    }
+
+    /// Black, red, yellow then white, for heatmaps
+    pub fn heat() -> Self {
+        Self::new(&[
+            Color::Rgb { r: 0, g: 0, b: 0 },
+            Color::Rgb { r: 200, g: 0, b: 0 },
+            Color::Rgb {
+                r: 255,
+                g: 200,
+                b: 0,
+            },
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+        ])
+    }
+
+    /// Red, yellow then green, for health bars
+    pub fn health() -> Self {
+        Self::new(&[
+            Color::Rgb { r: 220, g: 0, b: 0 },
+            Color::Rgb {
+                r: 240,
+                g: 220,
+                b: 0,
+            },
+            Color::Rgb { r: 0, g: 200, b: 0 },
+        ])
+    }
+
+    /// Every hue, from red back to red
+    pub fn rainbow() -> Self {
+        Self::new(
+            &(0..=6)
+                .map(|i| hsv(i as f32 * 60.0, 1.0, 1.0))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Dark purple, blue, green then yellow, a palette that stays readable for color blind people
+    pub fn viridis() -> Self {
+        Self::new(&[
+            Color::Rgb { r: 68, g: 1, b: 84 },
+            Color::Rgb {
+                r: 59,
+                g: 82,
+                b: 139,
+            },
+            Color::Rgb {
+                r: 33,
+                g: 145,
+                b: 140,
+            },
+            Color::Rgb {
+                r: 94,
+                g: 201,
+                b: 98,
+            },
+            Color::Rgb {
+                r: 253,
+                g: 231,
+                b: 37,
+            },
+        ])
+    }
+
+    /// Black to white
+    pub fn grayscale() -> Self {
+        Self::new(&[
+            Color::Rgb { r: 0, g: 0, b: 0 },
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+        ])
+    }
+}
+
+/// How a gradient is laid out when filling an area
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// From the start column to the end column
+    Horizontal,
+    /// From the start row to the end row
+    Vertical,
+    /// From the center to the borders
+    Radial,
+}
+
+/// The 16 base colors, with their RGB values in xterm
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of each channel in the 6x6x6 color cube of the 256 colors palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Perceptual distance between two colors, using the "redmean" approximation:
+/// the channels are weighted depending on how red the colors are, which is close to
+/// how the human eye perceives the difference, while staying cheap to compute
+pub fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let red_mean = (a.0 as i32 + b.0 as i32) / 2;
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    let red = ((512 + red_mean) * dr * dr) >> 8;
+    let blue = ((767 - red_mean) * db * db) >> 8;
+    (red + 4 * dg * dg + blue) as u32
+}
+
+/// Get the closest color of the 256 colors palette, among the color cube and the shades of grey
+pub fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|i| (CUBE_LEVELS[*i] as i32 - value as i32).abs())
+            .unwrap()
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    // the shades of grey go from 8 to 238 by steps of 10
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let grey_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let grey = 8 + 10 * grey_index;
+    if color_distance((r, g, b), (grey, grey, grey)) < color_distance((r, g, b), cube) {
+        232 + grey_index
+    } else {
+        16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8
+    }
+}
+
+/// Get the closest of the 16 base colors
+pub fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| color_distance((r, g, b), *rgb))
+        .unwrap()
+        .0
+}
+
+/// Get the RGB value of a color of the 256 colors palette
+pub fn ansi256_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => ANSI16[value as usize].1,
+        16..=231 => {
+            let index = value - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let grey = 8 + 10 * (value - 232);
+            (grey, grey, grey)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        ansi256_to_rgb, hsl, hsv, lerp, rgb_to_ansi16, rgb_to_ansi256, to_hsl, to_hsv, to_rgb,
+        ColorDepth, Gradient,
+    };
+    use crate::Color;
+
+    #[test]
+    fn detect() {
+        let detect = ColorDepth::from_env;
+        assert_eq!(
+            detect(Some("1"), Some("truecolor"), Some("xterm-256color")),
+            ColorDepth::NoColor
+        );
+        assert_eq!(
+            detect(Some(""), Some("truecolor"), Some("xterm")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect(None, None, Some("xterm-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            detect(None, None, Some("xterm-direct")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(None, None, Some("linux")), ColorDepth::Ansi16);
+        // serial consoles
+        assert_eq!(detect(None, None, Some("vt220")), ColorDepth::NoColor);
+        assert_eq!(
+            detect(None, Some("truecolor"), Some("dumb")),
+            ColorDepth::NoColor
+        );
+    }
+
+    #[test]
+    fn quantize() {
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+        assert_eq!(rgb_to_ansi256(255, 135, 0), 208);
+        assert_eq!(rgb_to_ansi16(250, 10, 10), Color::Red);
+        assert_eq!(rgb_to_ansi16(0, 0, 160), Color::DarkBlue);
+        assert_eq!(rgb_to_ansi16(120, 120, 130), Color::DarkGrey);
+        for value in 16..=255 {
+            let (r, g, b) = ansi256_to_rgb(value);
+            assert_eq!(rgb_to_ansi256(r, g, b), value);
+        }
+    }
+
+    #[test]
+    fn map() {
+        let orange = Color::Rgb {
+            r: 255,
+            g: 135,
+            b: 0,
+        };
+        assert_eq!(ColorDepth::TrueColor.map(orange), orange);
+        assert_eq!(ColorDepth::Ansi256.map(orange), Color::AnsiValue(208));
+        assert_eq!(ColorDepth::Ansi16.map(Color::AnsiValue(9)), Color::Red);
+        assert_eq!(ColorDepth::Ansi16.map(Color::AnsiValue(196)), Color::Red);
+        assert_eq!(ColorDepth::Ansi16.map(Color::Blue), Color::Blue);
+        assert_eq!(ColorDepth::NoColor.map(Color::Blue), Color::Reset);
+    }
+
+    #[test]
+    fn hsl_hsv() {
+        assert_eq!(hsl(0.0, 1.0, 0.5), Color::Rgb { r: 255, g: 0, b: 0 });
+        assert_eq!(hsl(120.0, 1.0, 0.25), Color::Rgb { r: 0, g: 128, b: 0 });
+        assert_eq!(hsv(240.0, 1.0, 1.0), Color::Rgb { r: 0, g: 0, b: 255 });
+        assert_eq!(
+            hsv(-60.0, 1.0, 1.0),
+            Color::Rgb {
+                r: 255,
+                g: 0,
+                b: 255
+            }
+        );
+        assert_eq!(to_hsv(Color::White), Some((0.0, 0.0, 1.0)));
+        assert_eq!(to_hsl(Color::Reset), None);
+        // round trips
+        for (r, g, b) in [(12, 200, 97), (255, 128, 0), (30, 30, 30), (90, 0, 250)] {
+            let color = Color::Rgb { r, g, b };
+            let (h, s, l) = to_hsl(color).unwrap();
+            assert_eq!(hsl(h, s, l), color);
+            let (h, s, v) = to_hsv(color).unwrap();
+            assert_eq!(hsv(h, s, v), color);
+        }
+    }
+
+    #[test]
+    fn gradients() {
+        let black = Color::Rgb { r: 0, g: 0, b: 0 };
+        let white = Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        };
+        let grey = Color::Rgb {
+            r: 128,
+            g: 128,
+            b: 128,
+        };
+        assert_eq!(lerp(black, Color::White, 0.5), grey);
+        assert_eq!(lerp(Color::Reset, white, 0.4), Color::Reset);
+        assert_eq!(to_rgb(Color::DarkBlue), Some((0, 0, 238)));
+
+        let gradient = Gradient::from_stops(&[(1.0, white), (0.0, black), (0.75, black)]);
+        assert_eq!(gradient.sample(-1.0), black);
+        assert_eq!(gradient.sample(0.5), black);
+        assert_eq!(gradient.sample(0.875), grey);
+        assert_eq!(gradient.sample(2.0), white);
+        assert_eq!(Gradient::grayscale().colors(3), vec![black, grey, white]);
+        assert_eq!(Gradient::new(&[white]).sample(0.5), white);
+    }
+}
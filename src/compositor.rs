@@ -0,0 +1,294 @@
+//! Layered composition of Screens
+//!
+//! A [Compositor] owns several named [Layer]s, each one being a [Screen] with an offset, a z-index,
+//! a visibility flag and transparency rules.
+//! The layers are flattened from the lowest to the highest z-index into a target Screen,
+//! or directly into the engine's screen with [ConsoleEngine::draw_compositor](../struct.ConsoleEngine.html#method.draw_compositor).
+//!
+//! see example `layers`
+
+use crossterm::style::Color;
+
+use crate::pixel::Pixel;
+use crate::screen::Screen;
+
+/// Transparency rule of a Layer
+#[derive(Clone, Debug)]
+pub enum Transparency {
+    /// Pixels having this character are not drawn
+    Char(char),
+    /// Pixels with a `Color::Reset` background keep the background of what's under them,
+    /// and are not drawn at all if they are spaces
+    Background,
+    /// The layer is only drawn where the mask (placed at the same offset) has a character other than a space or `'\u{0}'`
+    Mask(Screen),
+}
+
+/// A Screen placed inside a Compositor
+#[derive(Clone, Debug)]
+pub struct Layer {
+    /// Content of the layer
+    pub screen: Screen,
+    /// Horizontal offset of the layer
+    pub x: i32,
+    /// Vertical offset of the layer
+    pub y: i32,
+    /// Layers with a higher z-index are drawn over the ones with a lower z-index
+    pub z: i32,
+    /// Hidden layers are not drawn
+    pub visible: bool,
+    /// Transparency rules of the layer, all of them are applied
+    pub transparency: Vec<Transparency>,
+}
+
+impl Layer {
+    /// Creates a new visible and opaque layer at [0,0] with a z-index of 0
+    pub fn new(screen: Screen) -> Self {
+        Self {
+            screen,
+            x: 0,
+            y: 0,
+            z: 0,
+            visible: true,
+            transparency: vec![],
+        }
+    }
+
+    /// Changes the offset of the layer
+    pub fn with_offset(mut self, x: i32, y: i32) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    /// Changes the z-index of the layer
+    pub fn with_z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+
+    /// Adds a transparency rule to the layer
+    pub fn with_transparency(mut self, transparency: Transparency) -> Self {
+        self.transparency.push(transparency);
+        self
+    }
+
+    /// Blend a pixel of the layer with the pixel under it according to the transparency rules.
+    /// Returns `None` if nothing should be drawn
+    fn blend(&self, x: i32, y: i32, pixel: Pixel, under: Pixel) -> Option<Pixel> {
+        let mut pixel = pixel;
+        for rule in self.transparency.iter() {
+            match rule {
+                Transparency::Char(chr) => {
+                    if pixel.chr == *chr {
+                        return None;
+                    }
+                }
+                Transparency::Background => {
+                    if pixel.bg == Color::Reset {
+                        if pixel.chr == ' ' {
+                            return None;
+                        }
+                        pixel.bg = under.bg;
+                    }
+                }
+                Transparency::Mask(mask) => match mask.get_pxl(x, y) {
+                    Ok(mask_pixel) if mask_pixel.chr != ' ' && mask_pixel.chr != '\u{0}' => {}
+                    _ => return None,
+                },
+            }
+        }
+        Some(pixel)
+    }
+}
+
+/// Stack of named layers
+///
+/// usage:
+/// ```
+/// use console_engine::compositor::{Compositor, Layer, Transparency};
+/// use console_engine::screen::Screen;
+///
+/// let mut compositor = Compositor::new();
+/// compositor.add_layer("map", Layer::new(map_screen));
+/// compositor.add_layer(
+///     "hud",
+///     Layer::new(hud_screen).with_z(1).with_transparency(Transparency::Background),
+/// );
+///
+/// // move the hud around
+/// compositor.get_layer_mut("hud").unwrap().x += 1;
+///
+/// engine.draw_compositor(&compositor);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Compositor {
+    layers: Vec<(String, Layer)>,
+}
+
+impl Compositor {
+    /// Creates an empty compositor
+    pub fn new() -> Self {
+        Self { layers: vec![] }
+    }
+
+    /// Adds a layer to the compositor, replacing any layer with the same name
+    pub fn add_layer(&mut self, name: &str, layer: Layer) {
+        if let Some(existing) = self.get_layer_mut(name) {
+            *existing = layer;
+        } else {
+            self.layers.push((String::from(name), layer));
+        }
+    }
+
+    /// Removes a layer from the compositor, and returns it if it existed
+    pub fn remove_layer(&mut self, name: &str) -> Option<Layer> {
+        let index = self.layers.iter().position(|(n, _)| n == name)?;
+        Some(self.layers.remove(index).1)
+    }
+
+    /// Get a specific layer if it exists within the compositor
+    pub fn get_layer(&self, name: &str) -> Option<&Layer> {
+        self.layers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, layer)| layer)
+    }
+
+    /// Get a mutable reference to a specific layer if it exists within the compositor
+    pub fn get_layer_mut(&mut self, name: &str) -> Option<&mut Layer> {
+        self.layers
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, layer)| layer)
+    }
+
+    /// Shows or hides a layer, does nothing if the layer doesn't exist
+    pub fn set_visible(&mut self, name: &str, visible: bool) {
+        if let Some(layer) = self.get_layer_mut(name) {
+            layer.visible = visible;
+        }
+    }
+
+    /// Names of the layers, from the lowest to the highest z-index
+    pub fn layer_names(&self) -> Vec<&str> {
+        self.sorted_layers()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Layers sorted by z-index, layers with the same z-index keep their insertion order
+    fn sorted_layers(&self) -> Vec<&(String, Layer)> {
+        let mut layers: Vec<&(String, Layer)> = self.layers.iter().collect();
+        layers.sort_by_key(|(_, layer)| layer.z);
+        layers
+    }
+
+    /// Draws every visible layer into the target screen, from the lowest to the highest z-index
+    pub fn flatten(&self, target: &mut Screen) {
+        for (_, layer) in self.sorted_layers() {
+            if !layer.visible {
+                continue;
+            }
+            for j in 0..layer.screen.get_height() as i32 {
+                for i in 0..layer.screen.get_width() as i32 {
+                    let (x, y) = (layer.x + i, layer.y + j);
+                    // out of bounds pixels are ignored
+                    let under = match target.get_pxl(x, y) {
+                        Ok(under) => under,
+                        Err(_) => continue,
+                    };
+                    // unwrap here because we are sure that we won't get out of range
+                    let pixel = layer.screen.get_pxl(i, j).unwrap();
+                    if let Some(pixel) = layer.blend(i, j, pixel, under) {
+                        target.set_pxl(x, y, pixel);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Flattens the layers into a new screen of the given size
+    pub fn to_screen(&self, width: u32, height: u32) -> Screen {
+        let mut screen = Screen::new(width, height);
+        self.flatten(&mut screen);
+        screen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::Color;
+
+    use super::{Compositor, Layer, Transparency};
+    use crate::{pixel, screen::Screen};
+
+    fn filled(w: u32, h: u32, chr: char) -> Screen {
+        Screen::new_fill(w, h, pixel::pxl(chr))
+    }
+
+    #[test]
+    fn z_order() {
+        let mut compositor = Compositor::new();
+        compositor.add_layer("top", Layer::new(filled(2, 1, 'T')).with_z(2));
+        compositor.add_layer("bottom", Layer::new(filled(4, 1, 'B')));
+        compositor.add_layer("middle", Layer::new(filled(3, 1, 'M')).with_z(1));
+        assert_eq!(compositor.layer_names(), vec!["bottom", "middle", "top"]);
+        assert_eq!(compositor.to_screen(5, 1).to_text(), "TTMB ");
+
+        compositor.set_visible("middle", false);
+        assert_eq!(compositor.to_screen(5, 1).to_text(), "TTBB ");
+
+        compositor.get_layer_mut("top").unwrap().z = -1;
+        assert_eq!(compositor.to_screen(5, 1).to_text(), "BBBB ");
+
+        assert!(compositor.remove_layer("bottom").is_some());
+        assert!(compositor.get_layer("bottom").is_none());
+        assert_eq!(compositor.to_screen(5, 1).to_text(), "TT   ");
+    }
+
+    #[test]
+    fn offset() {
+        let mut compositor = Compositor::new();
+        compositor.add_layer("a", Layer::new(filled(2, 2, 'a')).with_offset(-1, 1));
+        compositor.add_layer("b", Layer::new(filled(2, 2, 'b')).with_offset(2, 2));
+        assert_eq!(compositor.to_screen(3, 3).to_text(), "   \na  \na b");
+    }
+
+    #[test]
+    fn transparency() {
+        let mut compositor = Compositor::new();
+        compositor.add_layer(
+            "base",
+            Layer::new(Screen::new_fill(4, 1, pixel::pxl_bg('.', Color::Blue))),
+        );
+
+        let mut hud = Screen::new(4, 1);
+        hud.print(0, 0, "a# b");
+        compositor.add_layer(
+            "hud",
+            Layer::new(hud)
+                .with_z(1)
+                .with_transparency(Transparency::Char('#'))
+                .with_transparency(Transparency::Background),
+        );
+        let screen = compositor.to_screen(4, 1);
+        assert_eq!(screen.to_text(), "a..b");
+        // the characters of the hud are drawn over the background of the base
+        assert_eq!(
+            screen.get_pxl(0, 0).unwrap(),
+            pixel::pxl_bg('a', Color::Blue)
+        );
+
+        let mut mask = Screen::new(4, 1);
+        mask.print(1, 0, "xx");
+        compositor.add_layer(
+            "hud",
+            Layer::new(filled(4, 1, 'm'))
+                .with_z(1)
+                .with_transparency(Transparency::Mask(mask)),
+        );
+        assert_eq!(compositor.to_screen(4, 1).to_text(), ".mm.");
+    }
+}
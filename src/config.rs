@@ -0,0 +1,261 @@
+//! Options used to initialize a ConsoleEngine
+
+use crate::backend::{Backend, CrosstermBackend};
+use crate::color::ColorDepth;
+use crate::{ConsoleEngine, Error};
+
+/// How the size of the engine's screen is chosen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EngineSize {
+    /// A screen of the given width and height,
+    /// the engine fails to start if the terminal is smaller and can't be resized
+    Fixed { width: u32, height: u32 },
+    /// A screen filling the entire terminal,
+    /// the engine fails to start if the terminal is smaller than the given minimum size and can't be resized
+    Fill { min_width: u32, min_height: u32 },
+    /// A screen of the given height drawn below the cursor, as wide as the terminal
+    ///
+    /// see [ConsoleEngine::init_inline](../struct.ConsoleEngine.html#method.init_inline)
+    Inline { height: u32 },
+}
+
+/// Options of a ConsoleEngine
+///
+/// The default configuration is the one used by [ConsoleEngine::init_fill](../struct.ConsoleEngine.html#method.init_fill) :
+/// the screen fills the terminal, which is set to raw mode, switched to the alternate screen, with a hidden cursor and mouse capture.
+/// A panic hook restores the terminal and exits the program.
+///
+/// usage:
+/// ```
+/// use console_engine::config::{EngineConfig, EngineSize};
+///
+/// let config = EngineConfig {
+///     size: EngineSize::Fixed { width: 20, height: 10 },
+///     mouse_capture: false,
+///     ..Default::default()
+/// };
+/// let mut engine = console_engine::ConsoleEngine::init_with_config(config).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EngineConfig {
+    /// Size of the screen
+    pub size: EngineSize,
+    /// Target frames per second, must be greater than zero
+    pub target_fps: u32,
+    /// Only produce frames on input, resize or redraw request,
+    /// see [ConsoleEngine::set_on_demand](../struct.ConsoleEngine.html#method.set_on_demand)
+    pub on_demand: bool,
+    /// Read the terminal input from a background thread, so custom events sent with an
+    /// [EventSender](../input/struct.EventSender.html) are received as soon as they are sent
+    pub input_thread: bool,
+    /// Enable raw mode, so inputs are received as they are typed and not echoed
+    pub raw_mode: bool,
+    /// Draw on the alternate screen, leaving the content of the terminal untouched.
+    /// Ignored by inline engines, which always draw on the main screen
+    pub alternate_screen: bool,
+    /// Hide the cursor while the engine runs
+    pub hide_cursor: bool,
+    /// Wrap each frame into a synchronized update, so the terminal displays it at once without tearing.
+    /// Terminals that don't support synchronized updates ignore it
+    pub synchronized_update: bool,
+    /// Colors the terminal is able to display, the colors of each frame being converted to the closest supported ones.
+    /// `None` asks the backend, which detects it from the environment, see [ColorDepth::detect]
+    pub color_depth: Option<ColorDepth>,
+    /// Capture mouse events. Terminals usually disable text selection while the mouse is captured
+    pub mouse_capture: bool,
+    /// Report when the terminal gains or loses the focus
+    pub focus_reporting: bool,
+    /// Receive pasted text at once instead of as a sequence of key presses
+    pub bracketed_paste: bool,
+    /// Install a panic hook restoring the terminal before the panic message is printed
+    pub panic_hook: bool,
+    /// Exit the program with the code 1 from the panic hook.
+    /// When disabled, the panic unwinds as usual after the terminal has been restored
+    pub exit_on_panic: bool,
+    /// Leave the last drawn frame on the terminal when the engine stops.
+    /// Only applies to inline engines
+    pub keep_last_frame: bool,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            size: EngineSize::Fill {
+                min_width: 0,
+                min_height: 0,
+            },
+            target_fps: 30,
+            on_demand: false,
+            input_thread: false,
+            raw_mode: true,
+            alternate_screen: true,
+            hide_cursor: true,
+            synchronized_update: true,
+            color_depth: None,
+            mouse_capture: true,
+            focus_reporting: false,
+            bracketed_paste: false,
+            panic_hook: true,
+            exit_on_panic: true,
+            keep_last_frame: false,
+        }
+    }
+}
+
+/// Builds a ConsoleEngine from an [EngineConfig], one option at a time
+///
+/// usage:
+/// ```
+/// use console_engine::ConsoleEngine;
+///
+/// // keep text selection and let our own panic handling run
+/// let mut engine = ConsoleEngine::builder()
+///     .fill_require(40, 20)
+///     .target_fps(60)
+///     .mouse_capture(false)
+///     .exit_on_panic(false)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EngineBuilder {
+    config: EngineConfig,
+}
+
+impl EngineBuilder {
+    /// Creates a builder with the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the configuration built so far
+    pub fn get_config(&self) -> &EngineConfig {
+        &self.config
+    }
+
+    /// Use a screen of the given width and height
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.config.size = EngineSize::Fixed { width, height };
+        self
+    }
+
+    /// Use a screen filling the entire terminal
+    pub fn fill(self) -> Self {
+        self.fill_require(0, 0)
+    }
+
+    /// Use a screen filling the entire terminal, requiring at least the given width and height
+    pub fn fill_require(mut self, min_width: u32, min_height: u32) -> Self {
+        self.config.size = EngineSize::Fill {
+            min_width,
+            min_height,
+        };
+        self
+    }
+
+    /// Use a screen of the given height below the cursor
+    pub fn inline(mut self, height: u32) -> Self {
+        self.config.size = EngineSize::Inline { height };
+        self
+    }
+
+    /// Set the target frames per second
+    pub fn target_fps(mut self, target_fps: u32) -> Self {
+        self.config.target_fps = target_fps;
+        self
+    }
+
+    /// Enable or disable the on demand mode
+    pub fn on_demand(mut self, enabled: bool) -> Self {
+        self.config.on_demand = enabled;
+        self
+    }
+
+    /// Enable or disable the input thread
+    pub fn input_thread(mut self, enabled: bool) -> Self {
+        self.config.input_thread = enabled;
+        self
+    }
+
+    /// Enable or disable raw mode
+    pub fn raw_mode(mut self, enabled: bool) -> Self {
+        self.config.raw_mode = enabled;
+        self
+    }
+
+    /// Draw on the alternate screen or not
+    pub fn alternate_screen(mut self, enabled: bool) -> Self {
+        self.config.alternate_screen = enabled;
+        self
+    }
+
+    /// Hide the cursor or not
+    pub fn hide_cursor(mut self, enabled: bool) -> Self {
+        self.config.hide_cursor = enabled;
+        self
+    }
+
+    /// Enable or disable synchronized updates
+    pub fn synchronized_update(mut self, enabled: bool) -> Self {
+        self.config.synchronized_update = enabled;
+        self
+    }
+
+    /// Use the given color depth instead of detecting it
+    pub fn color_depth(mut self, color_depth: ColorDepth) -> Self {
+        self.config.color_depth = Some(color_depth);
+        self
+    }
+
+    /// Enable or disable mouse capture
+    pub fn mouse_capture(mut self, enabled: bool) -> Self {
+        self.config.mouse_capture = enabled;
+        self
+    }
+
+    /// Enable or disable focus reporting
+    pub fn focus_reporting(mut self, enabled: bool) -> Self {
+        self.config.focus_reporting = enabled;
+        self
+    }
+
+    /// Enable or disable bracketed paste
+    pub fn bracketed_paste(mut self, enabled: bool) -> Self {
+        self.config.bracketed_paste = enabled;
+        self
+    }
+
+    /// Install the panic hook or not
+    pub fn panic_hook(mut self, enabled: bool) -> Self {
+        self.config.panic_hook = enabled;
+        self
+    }
+
+    /// Exit the program from the panic hook or not
+    pub fn exit_on_panic(mut self, enabled: bool) -> Self {
+        self.config.exit_on_panic = enabled;
+        self
+    }
+
+    /// Leave the last frame of an inline engine on the terminal or not
+    pub fn keep_last_frame(mut self, enabled: bool) -> Self {
+        self.config.keep_last_frame = enabled;
+        self
+    }
+
+    /// Starts an engine drawing to stdout
+    pub fn build(self) -> Result<ConsoleEngine, Error> {
+        self.build_with_backend(CrosstermBackend::default())
+    }
+
+    /// Starts an engine using the provided backend
+    pub fn build_with_backend<B: Backend>(self, backend: B) -> Result<ConsoleEngine<B>, Error> {
+        ConsoleEngine::init_with_config_and_backend(backend, self.config)
+    }
+}
+
+impl From<EngineConfig> for EngineBuilder {
+    fn from(config: EngineConfig) -> Self {
+        Self { config }
+    }
+}
@@ -0,0 +1,61 @@
+//! Errors returned by console_engine
+
+use std::fmt;
+
+/// Error returned by the fallible functions of console_engine
+///
+/// usage:
+/// ```
+/// use console_engine::Error;
+///
+/// // keep running when the terminal goes away
+/// if let Err(Error::Io(error)) = engine.try_draw() {
+///     eprintln!("terminal detached: {}", error);
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading from or writing to the terminal failed, for example because it has been closed
+    Io(std::io::Error),
+    /// The coordinates are outside of the screen, whose size is given
+    OutOfBounds {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "terminal I/O error: {}", error),
+            Error::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "coordinates [{}, {}] are out of bounds (size: [{}, {}])",
+                x, y, width, height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::OutOfBounds { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
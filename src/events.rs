@@ -1,13 +1,15 @@
 #![cfg(feature = "event")]
 use crossterm::event::{KeyEvent, MouseEvent};
 
+use crate::input::UserEvent;
+
 /// # ConsoleEngine events
 ///
 /// You can poll events with the `engine.poll` function.
 /// You may want to match the event to act according to which one has been returned
 ///
 /// See example `events`
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Event {
     /// A frame has passed. You can either call `engine.draw()` or do nothing.
     Frame,
@@ -19,4 +21,15 @@ pub enum Event {
     Mouse(MouseEvent),
     /// The window has been resized.
     Resize(u16, u16),
+    /// The terminal gained (`true`) or lost (`false`) the focus.
+    /// Only sent if focus reporting has been enabled, see [EngineBuilder](../config/struct.EngineBuilder.html#method.focus_reporting)
+    Focus(bool),
+    /// Some text has been pasted in the terminal.
+    /// Only sent if bracketed paste has been enabled, see [EngineBuilder](../config/struct.EngineBuilder.html#method.bracketed_paste)
+    Paste(String),
+    /// A custom event has been sent with an [EventSender](../input/struct.EventSender.html).
+    /// Use `downcast_ref` to get its value back
+    User(UserEvent),
+    /// A timer scheduled with `engine.set_timeout` or `engine.set_interval` fired, giving its id
+    Timer(u32),
 }
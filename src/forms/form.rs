@@ -25,8 +25,9 @@ use super::{FormError, FormField, FormOptions, FormValidationResult, FormValue};
 /// PageUp: scroll up (if available)  
 /// PageDown: scroll down (if available)  
 pub struct Form {
-    screen: Screen,
+    width: u32,
     height: u32,
+    content_height: u32,
     options: FormOptions,
     active: bool,
     index: usize,
@@ -39,8 +40,9 @@ impl Form {
     /// Constructs a new Form with the given width, height, style and options
     pub fn new(w: u32, h: u32, options: FormOptions) -> Self {
         Form {
-            screen: Screen::new(w, h),
+            width: w,
             height: h,
+            content_height: h,
             options,
             index: 0,
             active: false,
@@ -54,7 +56,7 @@ impl Form {
     ///
     /// The viewport is automatically clamped at its boundaries
     pub fn scroll(&mut self, amount: i32) {
-        let mut max_scroll = self.screen.get_height() as i64 - self.get_height() as i64;
+        let mut max_scroll = self.content_height as i64 - self.get_height() as i64;
         if self.options.style.border.is_some() {
             max_scroll += 1;
         }
@@ -150,6 +152,13 @@ impl Form {
         }
     }
 
+    /// Gives an event to the currently active field
+    fn forward_event(&mut self, event: Event) {
+        if let Some((_, field)) = self.fields.iter_mut().find(|(_, field)| field.is_active()) {
+            field.handle_event(event);
+        }
+    }
+
     /// Checks whenever the user went through the entire form, and confirmed on the last field
     pub fn is_finished(&self) -> bool {
         self.index >= self.fields.len()
@@ -186,7 +195,7 @@ impl FormField for Form {
     }
 
     fn get_width(&self) -> u32 {
-        self.screen.get_width()
+        self.width
     }
 
     fn get_height(&self) -> u32 {
@@ -197,8 +206,8 @@ impl FormField for Form {
     }
 
     fn resize(&mut self, w: u32, h: u32) {
+        self.width = w;
         self.height = h;
-        self.screen.resize(w, self.screen.get_height());
         for (_, field) in self.fields.iter_mut() {
             field.resize(w, field.get_height());
         }
@@ -235,15 +244,10 @@ impl FormField for Form {
                 KeyCode::PageUp => {
                     self.scroll(-1);
                 }
-                _ => {
-                    for (_, field) in self.fields.iter_mut() {
-                        if field.is_active() {
-                            field.handle_event(event);
-                            break;
-                        }
-                    }
-                }
+                _ => self.forward_event(event),
             }
+        } else if let Event::Paste(_) = event {
+            self.forward_event(event);
         }
     }
 
@@ -288,23 +292,28 @@ impl FormField for Form {
                 total_height += 1;
             }
         }
-        // resize the form screen if if doesn't fit anymore
-        if self.screen.get_height() != total_height {
-            self.screen.resize(self.screen.get_width(), total_height);
+        self.content_height = total_height;
+        let (width, height) = (self.get_width(), self.get_height());
+        if self.viewport.get_width() != width || self.viewport.get_height() != height {
+            self.viewport.resize(width, height);
         }
-        self.screen.fill(pixel::pxl_fbg(
+        self.viewport.fill(pixel::pxl_fbg(
             ' ',
             self.options.style.fg,
             self.options.style.bg,
         ));
         let padding = self.options.style.border.is_some() as i32;
 
+        // the content of the form is drawn through a view scrolled by the scroll index,
+        // so only the visible part of the form is drawn
+        let mut content = self
+            .viewport
+            .view(0, -(self.scroll_index as i32), width, total_height);
         let mut current_pos = padding;
         // display form label inside the form if there is no border
         if self.options.style.border.is_none() {
             if let Some(label) = self.options.label {
-                self.screen
-                    .print_fbg(1, 0, label, self.options.style.fg, self.options.style.bg);
+                content.print_fbg(1, 0, label, self.options.style.fg, self.options.style.bg);
                 current_pos = 1;
             }
         }
@@ -312,7 +321,7 @@ impl FormField for Form {
         for (_, field) in self.fields.iter_mut() {
             if field.should_display_label() {
                 if let Some(label) = field.get_options().label {
-                    self.screen.print_fbg(
+                    content.print_fbg(
                         padding,
                         current_pos,
                         label,
@@ -323,18 +332,9 @@ impl FormField for Form {
                 }
             }
 
-            self.screen
-                .print_screen(padding, current_pos, field.draw(tick));
+            content.print_screen(padding, current_pos, field.draw(tick));
             current_pos += field.get_height() as i32;
         }
-        // Extract the form into a viewport of the real size of the form
-        self.viewport = self.screen.extract(
-            0,
-            self.scroll_index as i32,
-            self.get_width() as i32 - 1,
-            self.scroll_index as i32 + self.get_height() as i32 - 1,
-            pixel::pxl(' '),
-        );
         if let Some(border) = self.options.style.border {
             // Display the border
             self.viewport.rect_border(
@@ -99,6 +99,7 @@ pub enum FormValue {
     Map(HashMap<String, FormValue>),
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for FormValue {
     fn default() -> Self {
         Self::Nothing
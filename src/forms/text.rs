@@ -61,6 +61,21 @@ impl Text {
         self.move_cursor(1);
     }
 
+    /// Insert a string at the position of the cursor, in one operation
+    ///
+    /// Control characters such as line breaks are ignored, since the field holds a single line
+    pub fn put_str(&mut self, string: &str) {
+        let inserted: Vec<char> = string.chars().filter(|chr| !chr.is_control()).collect();
+        self.input_buffer = self
+            .input_buffer
+            .chars()
+            .take(self.cursor_pos)
+            .chain(inserted.iter().copied())
+            .chain(self.input_buffer.chars().skip(self.cursor_pos))
+            .collect();
+        self.move_cursor(inserted.len() as i32);
+    }
+
     /// Removes a certain amount of characters either on the left (positive) or right (negative) side of the cursor
     pub fn remove_char(&mut self, amount: i32) {
         if amount == 0 {
@@ -149,6 +164,8 @@ impl FormField for Text {
                 }
                 _ => {}
             }
+        } else if let Event::Paste(text) = event {
+            self.put_str(&text);
         }
     }
 
@@ -269,6 +286,21 @@ impl HiddenText {
         self.move_cursor(1);
     }
 
+    /// Insert a string at the position of the cursor, in one operation
+    ///
+    /// Control characters such as line breaks are ignored, since the field holds a single line
+    pub fn put_str(&mut self, string: &str) {
+        let inserted: Vec<char> = string.chars().filter(|chr| !chr.is_control()).collect();
+        self.input_buffer = self
+            .input_buffer
+            .chars()
+            .take(self.cursor_pos)
+            .chain(inserted.iter().copied())
+            .chain(self.input_buffer.chars().skip(self.cursor_pos))
+            .collect();
+        self.move_cursor(inserted.len() as i32);
+    }
+
     /// Removes a certain amount of characters either on the left (positive) or right (negative) side of the cursor
     pub fn remove_char(&mut self, amount: i32) {
         if amount == 0 {
@@ -354,6 +386,8 @@ impl FormField for HiddenText {
                 }
                 _ => {}
             }
+        } else if let Event::Paste(text) = event {
+            self.put_str(&text);
         }
     }
 
@@ -421,3 +455,30 @@ impl FormField for HiddenText {
         &self.screen
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::events::Event;
+    use crate::forms::{FormField, FormOptions, FormValue};
+
+    use super::Text;
+
+    #[test]
+    fn paste() {
+        let mut text = Text::new(20, FormOptions::default());
+        text.set_active(true);
+        text.set_input_buffer("key=");
+        text.handle_event(Event::Paste(String::from("abc\r\n123")));
+        text.handle_event(Event::Key(KeyEvent::new(
+            KeyCode::Char('!'),
+            KeyModifiers::NONE,
+        )));
+        assert!(matches!(text.get_output(), FormValue::String(s) if s == "key=abc123!"));
+
+        text.move_cursor(i32::MIN);
+        text.handle_event(Event::Paste(String::from(">")));
+        assert!(matches!(text.get_output(), FormValue::String(s) if s == ">key=abc123!"));
+    }
+}
@@ -0,0 +1,182 @@
+//! Channel merging terminal input with custom events
+//!
+//! Every engine owns a channel that custom events can be sent into, from any thread,
+//! through an [EventSender]. When the input thread is enabled, terminal input is read
+//! in the background and sent into the same channel, so custom events wake the engine up immediately.
+
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SendError, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crossterm::event::Event;
+
+use crate::backend::EventReader;
+
+/// Payload of a custom event, retrieve its value with `downcast_ref`
+pub type UserEvent = Arc<dyn Any + Send + Sync>;
+
+/// How long the input thread waits for input before checking if it needs to stop
+const READ_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Message going through the channel of an engine
+pub(crate) enum Message {
+    Terminal(Event),
+    User(UserEvent),
+    /// The input thread failed to read the terminal, and stopped
+    Error(std::io::Error),
+}
+
+/// Sends custom events to an engine, from any thread
+///
+/// Get one with [ConsoleEngine::event_sender](../struct.ConsoleEngine.html#method.event_sender).
+/// Events are received from `poll` as `Event::User`, or from [get_user_events](../struct.ConsoleEngine.html#method.get_user_events) after `wait_frame`.
+///
+/// usage:
+/// ```
+/// let sender = engine.event_sender();
+/// std::thread::spawn(move || {
+///     for line in tail_log() {
+///         // stops once the engine has been dropped
+///         if sender.send(line).is_err() {
+///             break;
+///         }
+///     }
+/// });
+/// loop {
+///     match engine.poll() {
+///         Event::User(event) => {
+///             if let Some(line) = event.downcast_ref::<String>() {
+///                 // ...
+///             }
+///         }
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct EventSender {
+    sender: MessageSender,
+}
+
+impl EventSender {
+    pub(crate) fn new(sender: MessageSender) -> Self {
+        Self { sender }
+    }
+
+    /// Sends a custom event to the engine.
+    /// Fails, giving the event back, if the engine has been dropped
+    pub fn send<T: Any + Send + Sync>(&self, event: T) -> Result<(), SendError<UserEvent>> {
+        self.send_shared(Arc::new(event))
+    }
+
+    /// Sends a custom event that is already shared to the engine.
+    /// Fails, giving the event back, if the engine has been dropped
+    pub fn send_shared(&self, event: UserEvent) -> Result<(), SendError<UserEvent>> {
+        self.sender
+            .send(Message::User(event.clone()))
+            .map_err(|_| SendError(event))
+    }
+}
+
+/// Sending half of the channel of an engine, also waking up the async event stream waiting for a message
+#[derive(Clone, Debug)]
+pub(crate) struct MessageSender {
+    sender: Sender<Message>,
+    waker: Arc<Mutex<Option<Waker>>>,
+}
+
+impl MessageSender {
+    pub(crate) fn send(&self, message: Message) -> Result<(), SendError<Message>> {
+        self.sender.send(message)?;
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+}
+
+/// Channel of an engine, with the thread reading the terminal input if enabled
+pub(crate) struct Input {
+    sender: MessageSender,
+    receiver: Receiver<Message>,
+    thread: Option<(Arc<AtomicBool>, JoinHandle<()>)>,
+}
+
+impl Input {
+    pub(crate) fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender: MessageSender {
+                sender,
+                waker: Arc::new(Mutex::new(None)),
+            },
+            receiver,
+            thread: None,
+        }
+    }
+
+    pub(crate) fn sender(&self) -> EventSender {
+        EventSender::new(self.sender.clone())
+    }
+
+    /// Checks whenever terminal input is read by the input thread
+    pub(crate) fn is_threaded(&self) -> bool {
+        self.thread.is_some()
+    }
+
+    /// Starts reading the terminal input in the background
+    pub(crate) fn spawn(&mut self, mut reader: EventReader) {
+        if self.thread.is_some() {
+            return;
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let sender = self.sender.clone();
+        let handle = std::thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match reader(READ_TIMEOUT) {
+                    Ok(Some(event)) => {
+                        if sender.send(Message::Terminal(event)).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(error) => {
+                        // the engine stops the thread when it receives the error
+                        sender.send(Message::Error(error)).ok();
+                        break;
+                    }
+                }
+            }
+        });
+        self.thread = Some((stop, handle));
+    }
+
+    /// Stops the input thread, leaving the terminal input to the calling thread
+    pub(crate) fn stop(&mut self) {
+        if let Some((stop, handle)) = self.thread.take() {
+            stop.store(true, Ordering::Relaxed);
+            handle.join().ok();
+        }
+    }
+
+    /// Wakes the given waker up when the next message is sent
+    #[cfg(feature = "async")]
+    pub(crate) fn register_waker(&self, waker: &Waker) {
+        *self.sender.waker.lock().unwrap() = Some(waker.clone());
+    }
+
+    /// Takes the next message without waiting
+    pub(crate) fn try_recv(&self) -> Option<Message> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Waits up to the given timeout for the next message
+    pub(crate) fn recv_timeout(&self, timeout: Duration) -> Option<Message> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+}
@@ -0,0 +1,415 @@
+//! Constraint based layouts
+//!
+//! A [Layout] splits a [Rect] horizontally or vertically into several regions, each region being sized by a [Constraint].
+//! The resulting rectangles can be split again to build nested layouts.
+//!
+//! Layouts remember the last area they split, and only recompute the regions when the area changes,
+//! so they can be split at every frame using the size of the engine (see [ConsoleEngine::get_area](../struct.ConsoleEngine.html#method.get_area)).
+//!
+//! usage:
+//! ```
+//! use console_engine::layout::{Constraint, Layout, Margin};
+//!
+//! let mut columns = Layout::horizontal(vec![Constraint::Length(20), Constraint::Fill(1)]).margin(Margin::uniform(1));
+//! let mut rows = Layout::vertical(vec![Constraint::Length(3), Constraint::Fill(1), Constraint::Length(1)]);
+//!
+//! loop {
+//!     engine.wait_frame();
+//!     engine.check_resize();
+//!     engine.clear_screen();
+//!
+//!     let sidebar = columns.split(engine.get_area())[0];
+//!     let content = rows.split(columns.split(engine.get_area())[1]);
+//!     engine.rect(sidebar.x, sidebar.y, sidebar.right(), sidebar.bottom(), pixel::pxl('#'));
+//!     engine.print(content[0].x, content[0].y, "Title");
+//!     // ...
+//!     engine.draw();
+//! }
+//! ```
+
+/// Rectangular area of a Screen
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    /// Creates a new rectangle, starting at the given position
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Number of cells covered by the rectangle
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    /// Checks if the rectangle doesn't cover any cell
+    pub fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
+
+    /// Last column of the rectangle, as used by drawing functions like [Screen::rect](../screen/struct.Screen.html#method.rect)
+    pub fn right(&self) -> i32 {
+        self.x + self.width as i32 - 1
+    }
+
+    /// Last row of the rectangle, as used by drawing functions like [Screen::rect](../screen/struct.Screen.html#method.rect)
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height as i32 - 1
+    }
+
+    /// Checks if a position is inside the rectangle
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x <= self.right() && y <= self.bottom()
+    }
+
+    /// Area covered by both rectangles
+    pub fn intersection(&self, other: Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let bottom = (self.y + self.height as i32).min(other.y + other.height as i32);
+        if right <= x || bottom <= y {
+            return Rect::new(x, y, 0, 0);
+        }
+        Rect::new(x, y, (right - x) as u32, (bottom - y) as u32)
+    }
+
+    /// Shrinks the rectangle by a margin on each side
+    pub fn inner(&self, margin: Margin) -> Rect {
+        let horizontal = margin.left + margin.right;
+        let vertical = margin.top + margin.bottom;
+        if self.width < horizontal || self.height < vertical {
+            return Rect::new(self.x, self.y, 0, 0);
+        }
+        Rect::new(
+            self.x + margin.left as i32,
+            self.y + margin.top as i32,
+            self.width - horizontal,
+            self.height - vertical,
+        )
+    }
+}
+
+/// Space left on each side of a Rect
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Margin {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+impl Margin {
+    /// Creates a new margin, the sides being given clockwise starting from the top
+    pub fn new(top: u32, right: u32, bottom: u32, left: u32) -> Margin {
+        Margin {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Same margin on every side
+    pub fn uniform(margin: u32) -> Margin {
+        Margin::new(margin, margin, margin, margin)
+    }
+
+    /// Horizontal margin on the left and right sides, vertical margin on the top and bottom sides
+    pub fn symmetric(horizontal: u32, vertical: u32) -> Margin {
+        Margin::new(vertical, horizontal, vertical, horizontal)
+    }
+}
+
+/// Size of a region of a Layout
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    /// Fixed amount of cells
+    Length(u32),
+    /// Percentage of the available space
+    Percentage(u16),
+    /// Ratio of the available space, e.g. `Ratio(1, 3)` for a third
+    Ratio(u32, u32),
+    /// At least this amount of cells, grows to fill the remaining space if there is no `Fill` region
+    Min(u32),
+    /// At most this amount of cells, shrinks first if there isn't enough space
+    Max(u32),
+    /// Shares the remaining space with the other `Fill` regions, proportionally to its weight
+    Fill(u16),
+}
+
+/// Direction in which a Layout splits its area
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Regions are placed from left to right
+    Horizontal,
+    /// Regions are placed from top to bottom
+    Vertical,
+}
+
+/// Splits an area into regions
+///
+/// The margin is applied to the area before splitting it, the padding is applied inside each region.
+/// If the constraints need more space than available, `Max` regions shrink first, then the last regions are cut.
+///
+/// see the [module documentation](index.html) for usage
+#[derive(Clone, Debug)]
+pub struct Layout {
+    direction: Direction,
+    constraints: Vec<Constraint>,
+    margin: Margin,
+    padding: Margin,
+    cache: Option<(Rect, Vec<Rect>)>,
+}
+
+impl Layout {
+    /// Creates a new layout, with one region per constraint and no margin nor padding
+    pub fn new(direction: Direction, constraints: Vec<Constraint>) -> Layout {
+        Layout {
+            direction,
+            constraints,
+            margin: Margin::default(),
+            padding: Margin::default(),
+            cache: None,
+        }
+    }
+
+    /// Creates a layout placing its regions from left to right
+    pub fn horizontal(constraints: Vec<Constraint>) -> Layout {
+        Layout::new(Direction::Horizontal, constraints)
+    }
+
+    /// Creates a layout placing its regions from top to bottom
+    pub fn vertical(constraints: Vec<Constraint>) -> Layout {
+        Layout::new(Direction::Vertical, constraints)
+    }
+
+    /// Sets the margin around the whole layout
+    pub fn margin(mut self, margin: Margin) -> Layout {
+        self.margin = margin;
+        self.cache = None;
+        self
+    }
+
+    /// Sets the padding inside each region
+    pub fn padding(mut self, padding: Margin) -> Layout {
+        self.padding = padding;
+        self.cache = None;
+        self
+    }
+
+    /// Get the constraints of the layout
+    pub fn get_constraints(&self) -> &Vec<Constraint> {
+        &self.constraints
+    }
+
+    /// Changes the constraints of the layout
+    pub fn set_constraints(&mut self, constraints: Vec<Constraint>) {
+        self.constraints = constraints;
+        self.cache = None;
+    }
+
+    /// Splits an area into one rectangle per constraint
+    ///
+    /// The result is cached until the area or the layout changes
+    pub fn split(&mut self, area: Rect) -> Vec<Rect> {
+        if let Some((cached_area, regions)) = &self.cache {
+            if *cached_area == area {
+                return regions.clone();
+            }
+        }
+        let regions = self.compute(area);
+        self.cache = Some((area, regions.clone()));
+        regions
+    }
+
+    fn compute(&self, area: Rect) -> Vec<Rect> {
+        let inner = area.inner(self.margin);
+        let total = match self.direction {
+            Direction::Horizontal => inner.width,
+            Direction::Vertical => inner.height,
+        };
+        let sizes = solve(&self.constraints, total);
+
+        let mut position = 0;
+        sizes
+            .into_iter()
+            .map(|size| {
+                let region = match self.direction {
+                    Direction::Horizontal => {
+                        Rect::new(inner.x + position as i32, inner.y, size, inner.height)
+                    }
+                    Direction::Vertical => {
+                        Rect::new(inner.x, inner.y + position as i32, inner.width, size)
+                    }
+                };
+                position += size;
+                region.inner(self.padding)
+            })
+            .collect()
+    }
+}
+
+/// Computes the size of each region so that they fit in the total space
+fn solve(constraints: &[Constraint], total: u32) -> Vec<u32> {
+    let mut sizes: Vec<u32> = constraints
+        .iter()
+        .map(|constraint| match *constraint {
+            Constraint::Length(length) => length,
+            Constraint::Percentage(percentage) => {
+                (total as u64 * percentage.min(100) as u64 / 100) as u32
+            }
+            Constraint::Ratio(_, 0) => 0,
+            Constraint::Ratio(num, den) => {
+                (total as u64 * num as u64 / den as u64).min(total as u64) as u32
+            }
+            Constraint::Min(min) => min,
+            Constraint::Max(max) => max.min(total),
+            Constraint::Fill(_) => 0,
+        })
+        .collect();
+
+    // huge lengths must not overflow, they are cut below anyway
+    let used = sizes
+        .iter()
+        .fold(0u32, |used, size| used.saturating_add(*size));
+    if used < total {
+        // share the remaining space with the Fill regions, or the Min regions if there isn't any
+        let remaining = total - used;
+        let weights: Vec<u32> = constraints
+            .iter()
+            .map(|constraint| match constraint {
+                Constraint::Fill(weight) => *weight as u32,
+                _ => 0,
+            })
+            .collect();
+        let weights = if weights.iter().any(|w| *w > 0) {
+            weights
+        } else {
+            constraints
+                .iter()
+                .map(|constraint| matches!(constraint, Constraint::Min(_)) as u32)
+                .collect()
+        };
+        distribute(&mut sizes, &weights, remaining);
+    } else if used > total {
+        // shrink the Max regions first
+        let mut excess = used - total;
+        for (size, constraint) in sizes.iter_mut().zip(constraints) {
+            if let Constraint::Max(_) = constraint {
+                let shrink = excess.min(*size);
+                *size -= shrink;
+                excess -= shrink;
+            }
+        }
+    }
+
+    // cut the last regions if they still don't fit
+    let mut available = total;
+    for size in sizes.iter_mut() {
+        *size = (*size).min(available);
+        available -= *size;
+    }
+    sizes
+}
+
+/// Adds an amount of cells to the sizes, proportionally to the weights
+fn distribute(sizes: &mut [u32], weights: &[u32], amount: u32) {
+    let total_weight: u32 = weights.iter().sum();
+    if total_weight == 0 {
+        return;
+    }
+    let mut given = 0;
+    let mut cumulated_weight = 0;
+    for (size, weight) in sizes.iter_mut().zip(weights) {
+        if *weight == 0 {
+            continue;
+        }
+        // use the cumulated weight so that rounding errors don't leave any cell unused
+        cumulated_weight += weight;
+        let target = (amount as u64 * cumulated_weight as u64 / total_weight as u64) as u32;
+        *size += target - given;
+        given = target;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Constraint, Layout, Margin, Rect};
+
+    fn widths(layout: &mut Layout, width: u32) -> Vec<u32> {
+        layout
+            .split(Rect::new(0, 0, width, 1))
+            .iter()
+            .map(|r| r.width)
+            .collect()
+    }
+
+    #[test]
+    fn constraints() {
+        let mut layout = Layout::horizontal(vec![
+            Constraint::Length(10),
+            Constraint::Percentage(50),
+            Constraint::Fill(1),
+            Constraint::Fill(2),
+        ]);
+        assert_eq!(widths(&mut layout, 40), vec![10, 20, 3, 7]);
+
+        layout.set_constraints(vec![
+            Constraint::Ratio(1, 3),
+            Constraint::Min(5),
+            Constraint::Max(4),
+        ]);
+        assert_eq!(widths(&mut layout, 30), vec![10, 16, 4]);
+        // Max regions shrink first, then the last regions are cut
+        assert_eq!(widths(&mut layout, 16), vec![5, 7, 4]);
+        assert_eq!(widths(&mut layout, 12), vec![4, 5, 3]);
+        assert_eq!(widths(&mut layout, 6), vec![2, 4, 0]);
+
+        // oversized constraints are cut to the available space instead of overflowing
+        layout.set_constraints(vec![
+            Constraint::Length(u32::MAX),
+            Constraint::Min(u32::MAX),
+            Constraint::Fill(1),
+        ]);
+        assert_eq!(widths(&mut layout, 10), vec![10, 0, 0]);
+        layout.set_constraints(vec![Constraint::Percentage(300), Constraint::Ratio(5, 2)]);
+        assert_eq!(widths(&mut layout, 10), vec![10, 0]);
+        layout.set_constraints(vec![Constraint::Ratio(5, 2), Constraint::Percentage(300)]);
+        assert_eq!(widths(&mut layout, 10), vec![10, 0]);
+    }
+
+    #[test]
+    fn nesting() {
+        let area = Rect::new(0, 0, 20, 10);
+        let mut columns = Layout::horizontal(vec![Constraint::Length(5), Constraint::Fill(1)])
+            .margin(Margin::uniform(1));
+        let mut rows = Layout::vertical(vec![Constraint::Length(2), Constraint::Fill(1)])
+            .padding(Margin::symmetric(1, 0));
+
+        let regions = columns.split(area);
+        assert_eq!(regions, vec![Rect::new(1, 1, 5, 8), Rect::new(6, 1, 13, 8)]);
+        let content = rows.split(regions[1]);
+        assert_eq!(
+            content,
+            vec![Rect::new(7, 1, 11, 2), Rect::new(7, 3, 11, 6)]
+        );
+        assert_eq!((content[1].right(), content[1].bottom()), (17, 8));
+        assert!(content[1].contains(17, 8));
+        assert!(!content[1].contains(18, 8));
+
+        // a new area recomputes the regions
+        let regions = columns.split(Rect::new(0, 0, 30, 5));
+        assert_eq!(regions[1], Rect::new(6, 1, 23, 3));
+    }
+}
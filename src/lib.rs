@@ -6,30 +6,58 @@
 
 pub extern crate crossterm;
 
+pub mod asciicast;
+pub mod backend;
+pub mod canvas;
+pub mod color;
+pub mod compositor;
+pub mod config;
+mod error;
+pub mod input;
+pub mod layout;
 pub mod pixel;
+pub mod record;
 pub mod rect_style;
 pub mod screen;
+mod shapes;
+pub mod snapshot;
+pub mod timing;
 mod utils;
+pub mod view;
 
 #[cfg(feature = "event")]
 pub mod events;
 
 #[cfg(feature = "form")]
 pub mod forms;
-
+#[cfg(feature = "async")]
+pub mod stream;
+#[cfg(feature = "widgets")]
+pub mod widgets;
+
+use asciicast::AsciicastWriter;
+use backend::{Backend, CrosstermBackend};
+use color::{ColorDepth, Gradient, GradientDirection};
+use compositor::Compositor;
+use config::{EngineBuilder, EngineConfig, EngineSize};
 pub use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers, MouseButton};
-pub use crossterm::style::Color;
+pub use crossterm::style::{Attribute, Attributes, Color};
 use crossterm::terminal::{self, ClearType};
 use crossterm::{
-    event::{self, Event, KeyEvent, MouseEvent, MouseEventKind},
+    event::{Event, KeyEvent, MouseEvent, MouseEventKind},
     ErrorKind,
 };
 use crossterm::{execute, queue, style};
+pub use error::Error;
+use input::{EventSender, Input, Message, UserEvent};
 use pixel::Pixel;
+use record::{RecordedInput, Recorder, Recording, Replay, ReplaySpeed};
 use rect_style::BorderStyle;
 use screen::Screen;
-use std::io::Write;
-use std::io::{stdout, Stdout};
+use timing::{FrameStats, TimerHandle, Timers};
+
+/// How long an engine in on demand mode sleeps while waiting for input
+const IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
 
 /// Console Engine Framework
 ///
@@ -39,8 +67,23 @@ use std::io::{stdout, Stdout};
 ///
 /// - Build custom terminal display using [shapes](#method.line) or [text](#method.print)
 /// - Terminal handling with a [target frame per seconds](#method.init)
+/// - [Frame timing](#method.get_delta_time) statistics, see also [FixedTimestep](timing/struct.FixedTimestep.html)
+/// - [Timers](#method.set_interval) firing once or at a regular interval
+/// - Minimal [output](#method.get_draw_size) for each frame, wrapped in a synchronized update
+/// - [Color](color/index.html) conversions, blending and [gradients](#method.fill_gradient)
+/// - [Color depth](#method.set_color_depth) detection, converting the colors the terminal can't display
+/// - [Damage tracking](screen/struct.Screen.html#method.is_row_dirty), only comparing the rows modified since the last frame
+/// - [Text attributes](#method.print_attr) such as bold, italic or underlined text
 /// - [Keyboard](#method.is_key_pressed) and [mouse](#method.get_mouse_press) support
 /// - [Terminal resizing](#method.check_resize) support
+/// - [Fallible](#method.try_draw) drawing and input functions, returning an [Error](enum.Error.html)
+/// - [Input recording](#method.start_recording) and [replay](#method.start_replay)
+/// - [Asciicast recording](#method.start_asciicast) of the drawn frames
+/// - [Custom events](#method.event_sender) sent from other threads, merged with the terminal input
+/// - [Async event stream](#method.event_stream) and [async drawing](#method.draw_async) with feature `async`
+/// - Rendering to any output through a [Backend](backend/trait.Backend.html)
+/// - [Inline rendering](#method.init_inline) below the cursor, without taking over the whole terminal
+/// - [Configurable](#method.builder) terminal handling (mouse capture, alternate screen, panic hook, ...)
 ///
 /// # Basic Usage:
 ///
@@ -76,8 +119,8 @@ use std::io::{stdout, Stdout};
 /// #
 ///
 #[allow(clippy::needless_doctest_main)]
-pub struct ConsoleEngine {
-    stdout: Stdout,
+pub struct ConsoleEngine<B: Backend = CrosstermBackend> {
+    backend: B,
     time_limit: std::time::Duration,
     /// The current frame count, publicly accessible
     /// Has no purpose internally, use it as you want
@@ -87,67 +130,277 @@ pub struct ConsoleEngine {
     screen: Screen,
     screen_last_frame: Screen,
     instant: std::time::Instant,
+    frame_stats: FrameStats,
     keys_pressed: Vec<KeyEvent>,
     keys_held: Vec<KeyEvent>,
     keys_released: Vec<KeyEvent>,
     mouse_events: Vec<MouseEvent>,
     resize_events: Vec<(u16, u16)>,
+    pasted: String,
+    user_events: Vec<UserEvent>,
+    input: Input,
+    timers: Timers,
+    fired_timers: Vec<u32>,
+    recorder: Option<Recorder>,
+    replay: Option<Replay>,
+    asciicast: Option<AsciicastWriter<Box<dyn std::io::Write>>>,
+    focused: bool,
+    redraw_requested: bool,
+    color_depth: ColorDepth,
+    config: EngineConfig,
+    /// First terminal row of the screen, for inline engines
+    inline_origin: Option<u16>,
 }
 
 impl ConsoleEngine {
     /// Initialize a screen of the provided width and height, and load the target FPS
-    pub fn init(width: u32, height: u32, target_fps: u32) -> Result<ConsoleEngine, ErrorKind> {
-        assert!(target_fps > 0, "Target FPS needs to be greater than zero.");
+    pub fn init(width: u32, height: u32, target_fps: u32) -> Result<ConsoleEngine, Error> {
+        ConsoleEngine::init_with_backend(CrosstermBackend::default(), width, height, target_fps)
+    }
+
+    /// Initialize a screen filling the entire terminal with the target FPS
+    pub fn init_fill(target_fps: u32) -> Result<ConsoleEngine, Error> {
+        ConsoleEngine::init_fill_with_backend(CrosstermBackend::default(), target_fps)
+    }
+
+    /// Initialize an inline screen of the provided height below the cursor, with the target FPS.
+    ///
+    /// see [init_inline_with_backend](#method.init_inline_with_backend)
+    pub fn init_inline(height: u32, target_fps: u32) -> Result<ConsoleEngine, Error> {
+        ConsoleEngine::init_inline_with_backend(CrosstermBackend::default(), height, target_fps)
+    }
+
+    /// Initialize a screen filling the entire terminal with the target FPS
+    /// Also check the terminal width and height and assert if the terminal has at least the asked size
+    pub fn init_fill_require(
+        width: u32,
+        height: u32,
+        target_fps: u32,
+    ) -> Result<ConsoleEngine, Error> {
+        let builder = ConsoleEngine::builder()
+            .fill_require(width, height)
+            .target_fps(target_fps);
+        ConsoleEngine::init_with_config(*builder.get_config())
+    }
+
+    /// Initialize a screen with the given options
+    ///
+    /// see [EngineConfig](config/struct.EngineConfig.html)
+    pub fn init_with_config(config: EngineConfig) -> Result<ConsoleEngine, Error> {
+        ConsoleEngine::init_with_config_and_backend(CrosstermBackend::default(), config)
+    }
+
+    /// Start building an engine with custom options, such as disabling the mouse capture or the alternate screen
+    ///
+    /// see [EngineBuilder](config/struct.EngineBuilder.html)
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::new()
+    }
+}
+
+impl<B: Backend> ConsoleEngine<B> {
+    /// Initialize a screen of the provided width and height, and load the target FPS.
+    /// The engine will use the provided backend instead of stdout
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::backend::CrosstermBackend;
+    ///
+    /// let log = std::fs::File::create("frames.log").unwrap();
+    /// let mut engine = console_engine::ConsoleEngine::init_with_backend(CrosstermBackend::new(log), 20, 10, 3).unwrap();
+    /// ```
+    pub fn init_with_backend(
+        backend: B,
+        width: u32,
+        height: u32,
+        target_fps: u32,
+    ) -> Result<ConsoleEngine<B>, Error> {
+        let config = EngineConfig {
+            size: EngineSize::Fixed { width, height },
+            target_fps,
+            ..Default::default()
+        };
+        ConsoleEngine::init_with_config_and_backend(backend, config)
+    }
+
+    /// Initialize a screen filling the entire terminal with the target FPS.
+    /// The engine will use the provided backend instead of stdout
+    pub fn init_fill_with_backend(backend: B, target_fps: u32) -> Result<ConsoleEngine<B>, Error> {
+        let config = EngineConfig {
+            target_fps,
+            ..Default::default()
+        };
+        ConsoleEngine::init_with_config_and_backend(backend, config)
+    }
+
+    /// Initialize an inline screen of the provided height, and load the target FPS.
+    /// The engine will use the provided backend instead of stdout
+    ///
+    /// Instead of taking over the whole terminal with the alternate screen,
+    /// the engine reserves `height` lines below the cursor (scrolling the terminal if needed)
+    /// and only draws in this region. The screen is as wide as the terminal.
+    ///
+    /// The region is cleared when the engine stops,
+    /// unless [set_keep_last_frame](#method.set_keep_last_frame) has been called.
+    /// Mouse events are reported relatively to the region, rows above it are reported as the row 0.
+    ///
+    /// usage:
+    /// ```
+    /// // reserve 3 lines below the cursor
+    /// let mut engine = console_engine::ConsoleEngine::init_inline(3, 30).unwrap();
+    /// engine.set_keep_last_frame(true);
+    /// for step in 0..=100 {
+    ///     engine.wait_frame();
+    ///     engine.clear_screen();
+    ///     engine.print(0, 1, &format!("Downloading... {}%", step));
+    ///     engine.draw();
+    /// }
+    /// ```
+    pub fn init_inline_with_backend(
+        backend: B,
+        height: u32,
+        target_fps: u32,
+    ) -> Result<ConsoleEngine<B>, Error> {
+        let config = EngineConfig {
+            size: EngineSize::Inline { height },
+            target_fps,
+            ..Default::default()
+        };
+        ConsoleEngine::init_with_config_and_backend(backend, config)
+    }
+
+    /// Initialize a screen with the given options.
+    /// The engine will use the provided backend instead of stdout
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::backend::HeadlessBackend;
+    /// use console_engine::config::EngineConfig;
+    ///
+    /// let config = EngineConfig {
+    ///     panic_hook: false,
+    ///     ..Default::default()
+    /// };
+    /// let mut engine = console_engine::ConsoleEngine::init_with_config_and_backend(HeadlessBackend::new(20, 10), config).unwrap();
+    /// ```
+    pub fn init_with_config_and_backend(
+        backend: B,
+        config: EngineConfig,
+    ) -> Result<ConsoleEngine<B>, Error> {
+        assert!(
+            config.target_fps > 0,
+            "Target FPS needs to be greater than zero."
+        );
+        let (term_width, term_height) = backend.size()?;
+        let (width, height) = match config.size {
+            EngineSize::Fixed { width, height } => (width, height),
+            EngineSize::Fill { .. } => (term_width as u32, term_height as u32),
+            EngineSize::Inline { height } => (
+                term_width as u32,
+                height.clamp(1, term_height.max(1) as u32),
+            ),
+        };
+        let color_depth = config.color_depth.unwrap_or_else(|| backend.color_depth());
         let mut engine = ConsoleEngine {
-            stdout: stdout(),
-            time_limit: std::time::Duration::from_millis(1000 / target_fps as u64),
+            backend,
+            time_limit: std::time::Duration::from_millis(1000 / config.target_fps as u64),
             frame_count: 0,
             width,
             height,
             screen: Screen::new(width, height),
             screen_last_frame: Screen::new_empty(width, height),
             instant: std::time::Instant::now(),
+            frame_stats: FrameStats::new(),
             keys_pressed: vec![],
             keys_held: vec![],
             keys_released: vec![],
             mouse_events: vec![],
             resize_events: vec![],
+            pasted: String::new(),
+            user_events: vec![],
+            input: Input::new(),
+            timers: Timers::default(),
+            fired_timers: vec![],
+            recorder: None,
+            replay: None,
+            asciicast: None,
+            focused: true,
+            redraw_requested: true,
+            color_depth,
+            config,
+            inline_origin: None,
         };
-        let previous_panic_hook = std::panic::take_hook();
-        std::panic::set_hook(Box::new(move |panic_info| {
-            Self::handle_panic(panic_info);
-            previous_panic_hook(panic_info);
-            std::process::exit(1);
-        }));
+        if config.panic_hook {
+            if let Some(panic_handler) = engine.backend.panic_handler(&config) {
+                let exit_on_panic = config.exit_on_panic;
+                let previous_panic_hook = std::panic::take_hook();
+                std::panic::set_hook(Box::new(move |panic_info| {
+                    panic_handler();
+                    previous_panic_hook(panic_info);
+                    if exit_on_panic {
+                        std::process::exit(1);
+                    }
+                }));
+            }
+        }
         engine.begin()?;
-        engine.try_resize(width, height)?;
+        match config.size {
+            EngineSize::Fixed { width, height } => engine.try_resize(width, height)?,
+            EngineSize::Fill {
+                min_width,
+                min_height,
+            } => engine.try_resize(min_width, min_height)?,
+            EngineSize::Inline { .. } => {}
+        }
+        if config.input_thread {
+            if let Some(reader) = engine.backend.event_reader() {
+                engine.input.spawn(reader);
+            }
+        }
         Ok(engine)
     }
 
-    /// Initialize a screen filling the entire terminal with the target FPS
-    pub fn init_fill(target_fps: u32) -> Result<ConsoleEngine, ErrorKind> {
-        let size = crossterm::terminal::size()?;
-        ConsoleEngine::init(size.0 as u32, size.1 as u32, target_fps)
+    /// Get a reference to the backend used by the engine
+    pub fn backend(&self) -> &B {
+        &self.backend
     }
 
-    /// Initialize a screen filling the entire terminal with the target FPS
-    /// Also check the terminal width and height and assert if the terminal has at least the asked size
-    pub fn init_fill_require(
-        width: u32,
-        height: u32,
-        target_fps: u32,
-    ) -> Result<ConsoleEngine, ErrorKind> {
-        let mut engine = ConsoleEngine::init_fill(target_fps)?;
-        engine.try_resize(width, height)?;
-        Ok(engine)
+    /// Get a mutable reference to the backend used by the engine
+    pub fn backend_mut(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    /// Get the options the engine has been initialized with
+    pub fn get_config(&self) -> &EngineConfig {
+        &self.config
+    }
+
+    /// Get the colors the terminal is able to display
+    pub fn get_color_depth(&self) -> ColorDepth {
+        self.color_depth
+    }
+
+    /// Changes the colors the terminal is able to display, and redraws the screen with them.
+    /// The colors of the screen are kept, only their output is converted
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::color::ColorDepth;
+    ///
+    /// // let the user disable colors from the application
+    /// engine.set_color_depth(ColorDepth::NoColor);
+    /// ```
+    pub fn set_color_depth(&mut self, color_depth: ColorDepth) {
+        self.color_depth = color_depth;
+        self.request_full_draw();
     }
 
     /// Try to resize the terminal to match the asked width and height at minimum
     fn try_resize(&mut self, width: u32, height: u32) -> Result<(), ErrorKind> {
-        let size = crossterm::terminal::size()?;
+        let size = self.backend.size()?;
         if (size.0 as u32) < width || (size.1 as u32) < height {
             execute!(
-                self.stdout,
+                self.backend,
                 crossterm::terminal::SetSize(width as u16, height as u16),
                 crossterm::terminal::SetSize(width as u16, height as u16)
             )?;
@@ -156,11 +409,11 @@ impl ConsoleEngine {
             #[cfg(feature = "event")]
             use std::time::Duration;
             #[cfg(feature = "event")]
-            while let Ok(true) = event::poll(Duration::from_micros(100)) {
-                event::read().ok();
+            while let Ok(true) = self.backend.poll_event(Duration::from_micros(100)) {
+                self.backend.read_event().ok();
             }
         }
-        if crossterm::terminal::size()? < (width as u16, height as u16) {
+        if self.backend.size()? < (width as u16, height as u16) {
             Err(ErrorKind::new(std::io::ErrorKind::Other, format!("Your terminal must have at least a width and height of {}x{} characters. Currently has {}x{}", width, height, size.0, size.1)))
         } else {
             Ok(())
@@ -169,50 +422,137 @@ impl ConsoleEngine {
 
     /// Initializes the internal components such as hiding the cursor
     fn begin(&mut self) -> Result<(), ErrorKind> {
-        terminal::enable_raw_mode().unwrap();
-        execute!(
-            self.stdout,
-            terminal::EnterAlternateScreen,
-            terminal::Clear(ClearType::All),
-            crossterm::cursor::Hide,
-            crossterm::cursor::MoveTo(0, 0),
-            crossterm::event::EnableMouseCapture
+        if self.config.raw_mode {
+            self.backend.enable_raw_mode()?;
+        }
+        if matches!(self.config.size, EngineSize::Inline { .. }) {
+            self.begin_inline()?;
+        } else {
+            if self.config.alternate_screen {
+                queue!(self.backend, terminal::EnterAlternateScreen)?;
+            }
+            queue!(
+                self.backend,
+                terminal::Clear(ClearType::All),
+                crossterm::cursor::MoveTo(0, 0)
+            )?;
+        }
+        if self.config.hide_cursor {
+            queue!(self.backend, crossterm::cursor::Hide)?;
+        }
+        if self.config.mouse_capture {
+            queue!(self.backend, crossterm::event::EnableMouseCapture)?;
+        }
+        if self.config.focus_reporting {
+            queue!(self.backend, crossterm::event::EnableFocusChange)?;
+        }
+        if self.config.bracketed_paste {
+            queue!(self.backend, crossterm::event::EnableBracketedPaste)?;
+        }
+        self.backend.flush()
+    }
+
+    /// Reserves the lines used by an inline engine below the cursor
+    fn begin_inline(&mut self) -> Result<(), ErrorKind> {
+        let (_, term_height) = self.backend.size()?;
+        let last_row = term_height.saturating_sub(1);
+        let (column, mut row) = self.backend.cursor_position()?;
+        // start on a new line if the cursor is after some text
+        if column > 0 {
+            queue!(self.backend, style::Print("\r\n"))?;
+            row = (row + 1).min(last_row);
+        }
+        // print new lines instead of moving the cursor,
+        // so the terminal scrolls and keeps what was above in its scrollback
+        let lines = self.height as u16;
+        for _ in 1..lines {
+            queue!(self.backend, style::Print("\r\n"))?;
+        }
+        let origin = (row + lines - 1).min(last_row) + 1 - lines;
+        self.inline_origin = Some(origin);
+        queue!(
+            self.backend,
+            crossterm::cursor::MoveTo(0, origin),
+            terminal::Clear(ClearType::FromCursorDown)
         )
     }
 
     /// Gracefully stop the engine, and set back a visible cursor
+    /// Errors are ignored, since the terminal may already be gone
     fn end(&mut self) {
+        self.input.stop();
+        if self.config.bracketed_paste {
+            queue!(self.backend, crossterm::event::DisableBracketedPaste).ok();
+        }
+        if self.config.focus_reporting {
+            queue!(self.backend, crossterm::event::DisableFocusChange).ok();
+        }
+        if self.config.mouse_capture {
+            queue!(self.backend, crossterm::event::DisableMouseCapture).ok();
+        }
         execute!(
-            self.stdout,
+            self.backend,
             crossterm::cursor::Show,
+            style::SetAttribute(Attribute::Reset),
             style::SetBackgroundColor(Color::Reset),
-            style::SetForegroundColor(Color::Reset),
-            crossterm::event::DisableMouseCapture,
-            terminal::LeaveAlternateScreen
+            style::SetForegroundColor(Color::Reset)
         )
-        .unwrap();
-        terminal::disable_raw_mode().unwrap();
+        .ok();
+        match self.inline_origin {
+            Some(origin) if !self.config.keep_last_frame => {
+                execute!(
+                    self.backend,
+                    crossterm::cursor::MoveTo(0, origin),
+                    terminal::Clear(ClearType::FromCursorDown)
+                )
+                .ok();
+            }
+            None if self.config.alternate_screen => {
+                execute!(self.backend, terminal::LeaveAlternateScreen).ok();
+            }
+            origin => {
+                // the last frame stays on the main screen, leave the cursor on the line after it
+                execute!(
+                    self.backend,
+                    crossterm::cursor::MoveTo(
+                        0,
+                        origin.unwrap_or(0) + self.height.saturating_sub(1) as u16
+                    ),
+                    style::Print("\r\n")
+                )
+                .ok();
+            }
+        }
+        if self.config.raw_mode {
+            self.backend.disable_raw_mode().ok();
+        }
     }
 
-    /// stops the engine when a panic occurs
-    /// Similar to the end function, but without the engine instance.
-    /// So we assume we used stdout, and free it.
-    fn handle_panic(_panic_info: &std::panic::PanicInfo) {
-        execute!(
-            stdout(),
-            crossterm::cursor::Show,
-            style::SetBackgroundColor(Color::Reset),
-            style::SetForegroundColor(Color::Reset),
-            crossterm::event::DisableMouseCapture,
-            terminal::LeaveAlternateScreen
-        )
-        .unwrap();
-        terminal::disable_raw_mode().unwrap();
+    /// Checks whenever the engine draws inline, below the cursor
+    ///
+    /// see [init_inline](#method.init_inline)
+    pub fn is_inline(&self) -> bool {
+        self.inline_origin.is_some()
+    }
+
+    /// Leave the last drawn frame on the terminal when the engine stops, instead of clearing it.
+    /// Only applies to inline engines, since other engines leave the alternate screen
+    pub fn set_keep_last_frame(&mut self, keep: bool) {
+        self.config.keep_last_frame = keep;
+    }
+
+    /// Converts the coordinates of a mouse event to be relative to the inline region
+    fn translate_mouse(&self, event: MouseEvent) -> MouseEvent {
+        let mut event = event;
+        if let Some(origin) = self.inline_origin {
+            event.row = event.row.saturating_sub(origin);
+        }
+        event
     }
 
     /// Set the terminal's title
     pub fn set_title(&mut self, title: &str) {
-        execute!(self.stdout, crossterm::terminal::SetTitle(title)).ok();
+        execute!(self.backend, crossterm::terminal::SetTitle(title)).ok();
     }
 
     /// Get the screen width
@@ -225,6 +565,20 @@ impl ConsoleEngine {
         self.screen.get_height()
     }
 
+    /// Creates a view on a region of the screen
+    ///
+    /// see [Screen::view](screen/struct.Screen.html#method.view)
+    pub fn view(&mut self, x: i32, y: i32, width: u32, height: u32) -> view::ScreenView<'_> {
+        self.screen.view(x, y, width, height)
+    }
+
+    /// Get the area covered by the screen, to be split with a [Layout](layout/struct.Layout.html)
+    ///
+    /// The area follows the size of the screen, so layouts are recomputed after a resize
+    pub fn get_area(&self) -> layout::Rect {
+        self.screen.get_area()
+    }
+
     /// Reset the screen to a blank state
     pub fn clear_screen(&mut self) {
         self.screen.clear()
@@ -261,6 +615,42 @@ impl ConsoleEngine {
         self.screen.print_fbg(x, y, string, fg, bg)
     }
 
+    /// prints a string at the specified coordinates with the specified text attributes
+    /// The string will be cropped if it reach the right border
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::Attribute;
+    ///
+    /// // print "Hello, world" in bold
+    /// engine.print_attr(0,0, "Hello, world!", Attribute::Bold.into());
+    /// ```
+    pub fn print_attr(&mut self, x: i32, y: i32, string: &str, attr: Attributes) {
+        self.screen.print_attr(x, y, string, attr)
+    }
+
+    /// prints a string at the specified coordinates with the specified foreground and background color and text attributes
+    /// The string will be cropped if it reach the right border
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::{Attribute, Attributes, Color};
+    ///
+    /// // print an underlined and bold "Hello, world" in blue on white background
+    /// engine.print_fbg_attr(0,0, "Hello, world!", Color::Blue, Color::White, Attributes::from(Attribute::Bold) | Attribute::Underlined);
+    /// ```
+    pub fn print_fbg_attr(
+        &mut self,
+        x: i32,
+        y: i32,
+        string: &str,
+        fg: Color,
+        bg: Color,
+        attr: Attributes,
+    ) {
+        self.screen.print_fbg_attr(x, y, string, fg, bg, attr)
+    }
+
     /// Prints another screen on specified coordinates.
     /// Useful when you want to manage several "subscreen"
     ///
@@ -283,6 +673,22 @@ impl ConsoleEngine {
         self.screen.print_screen(x, y, source)
     }
 
+    /// Draws a high resolution canvas on specified coordinates
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::canvas::{Canvas, CanvasMode};
+    /// use console_engine::Color;
+    ///
+    /// let mut canvas = Canvas::new(10, 5, CanvasMode::HalfBlock);
+    /// canvas.fill_circle(5, 5, 4, Color::Red);
+    ///
+    /// engine.blit_canvas(2, 2, &canvas);
+    /// ```
+    pub fn blit_canvas(&mut self, x: i32, y: i32, canvas: &canvas::Canvas) {
+        canvas.blit(&mut self.screen, x, y)
+    }
+
     /// Prints another screen on specified coordinates, ignoring a specific character while printing
     /// Ignoring a character will behave like transparency
     ///
@@ -359,6 +765,27 @@ impl ConsoleEngine {
             .fill_rect(start_x, start_y, end_x, end_y, character)
     }
 
+    /// Fill a rectangle between two sets of coordinates with the colors of a gradient, as background of blank pixels
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::color::{Gradient, GradientDirection};
+    /// // ...
+    /// engine.fill_gradient(0, 0, 19, 9, &Gradient::heat(), GradientDirection::Radial);
+    /// ```
+    pub fn fill_gradient(
+        &mut self,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        gradient: &Gradient,
+        direction: GradientDirection,
+    ) {
+        self.screen
+            .fill_gradient(start_x, start_y, end_x, end_y, gradient, direction)
+    }
+
     /// Draws a circle of the provided character at an x and y position with a radius
     /// see: [olcPixelGameEngine Repository](https://github.com/OneLoneCoder/olcPixelGameEngine)
     ///
@@ -475,7 +902,7 @@ impl ConsoleEngine {
     ///     engine.print(0,0,"Found a 'o'");
     /// }
     /// ```
-    pub fn get_pxl(&self, x: i32, y: i32) -> Result<Pixel, String> {
+    pub fn get_pxl(&self, x: i32, y: i32) -> Result<Pixel, Error> {
         self.screen.get_pxl(x, y)
     }
 
@@ -491,6 +918,11 @@ impl ConsoleEngine {
         self.width = new_width;
         self.height = new_height;
         self.screen_last_frame = Screen::new_empty(self.width, self.height);
+        if let Some(asciicast) = &mut self.asciicast {
+            if asciicast.write_resize(new_width, new_height).is_err() {
+                self.asciicast = None;
+            }
+        }
     }
 
     /// Extracts part of the current screen as a separate Screen object
@@ -544,7 +976,7 @@ impl ConsoleEngine {
     /// Returns a clone of the current screen
     ///
     /// You can keep it into a variable to restore the screen later, via `set_screen`.
-    /// You can then use the to_string method to write the screen in a file for example
+    /// You can then use the [to_text](screen/struct.Screen.html#method.to_text) method to write the screen in a file for example
     ///
     /// see [set_screen](#method.set_screen) for a more complete example
     ///
@@ -569,76 +1001,319 @@ impl ConsoleEngine {
     /// engine.print(0,0,"Hello, world!"); // <- prints "Hello, world!" in 'screen' memory
     /// engine.draw(); // display 'screen' memory to the user's terminal
     /// ```
+    ///
+    /// Panics if the terminal can't be written to, see [try_draw](#method.try_draw)
     pub fn draw(&mut self) {
+        self.try_draw().unwrap();
+    }
+
+    /// Draw the screen in the terminal like [draw](#method.draw),
+    /// returning an error instead of panicking if the terminal can't be written to
+    ///
+    /// usage:
+    /// ```
+    /// // the terminal may be detached at any time
+    /// if engine.try_draw().is_err() {
+    ///     attached = false;
+    /// }
+    /// ```
+    pub fn try_draw(&mut self) -> Result<(), Error> {
         // we use the queue! macro to store in one-shot the screen we'll write.
         // This is an optimization because we write all we need once instead of writing small bit of screen by small bit of screen.
         // Actually, this does not change much for Linux terminals (like 5 fps gained from this)
         // But for windows terminal we can see huge improvements (example lines-fps goes from 35-40 fps to 65-70 for a 100x50 term)
-        // reset cursor position
-        queue!(self.stdout, crossterm::cursor::MoveTo(0, 0)).unwrap();
+        let draw_start = std::time::Instant::now();
+        let origin = self.inline_origin.unwrap_or(0);
+        let frame = self.encode_frame(origin)?;
+        // flush the buffer into user's terminal
+        let written = self
+            .backend
+            .write_all(&frame)
+            .and_then(|_| self.backend.flush());
+        if let Err(error) = written {
+            // the terminal may have received part of the frame, the next one can't be a diff
+            self.request_full_draw();
+            return Err(error.into());
+        }
+        self.write_asciicast_frame(&frame, origin);
+        self.backend.present(&self.screen);
+        self.store_last_frame();
+        self.frame_stats.push_draw_time(draw_start.elapsed());
+        self.frame_stats.push_draw_size(frame.len());
+        Ok(())
+    }
+
+    /// Stores the frame for the next draw call, only copying the rows modified since the last one
+    fn store_last_frame(&mut self) {
+        let same_size = self.screen_last_frame.get_width() == self.screen.get_width()
+            && self.screen_last_frame.get_height() == self.screen.get_height();
+        if self.screen_last_frame.is_empty() || !same_size {
+            self.screen_last_frame = self.screen.clone();
+        } else {
+            self.screen_last_frame.copy_dirty_rows(&self.screen);
+        }
+        self.screen.clear_dirty();
+    }
+
+    /// Copies the output of a frame into the asciicast recording, stopping the recording if its output fails
+    fn write_asciicast_frame(&mut self, frame: &[u8], origin: u16) {
+        if let Some(asciicast) = &mut self.asciicast {
+            let result = if origin == 0 {
+                asciicast.write_output(frame)
+            } else {
+                // the recording only contains the lines of an inline engine, starting at the top
+                let mut buffer = vec![];
+                Self::write_frame(
+                    &mut buffer,
+                    &self.screen,
+                    &mut self.screen_last_frame,
+                    0,
+                    self.color_depth,
+                )
+                .and_then(|_| asciicast.write_output(&buffer))
+            };
+            if result.is_err() {
+                self.asciicast = None;
+            }
+        }
+    }
+
+    /// Starts recording the drawn frames as an asciicast v2 file, that can be played with `asciinema`
+    ///
+    /// The recording has the size of the screen, and starts with a full frame on the next [draw](#method.draw).
+    /// The output of each draw is written with its timestamp, along with the resizes of the screen.
+    /// The recording stops if the output fails.
+    ///
+    /// see [asciicast](asciicast/index.html)
+    ///
+    /// usage:
+    /// ```
+    /// let file = std::fs::File::create("demo.cast").unwrap();
+    /// engine.start_asciicast(std::io::BufWriter::new(file), Some("my demo")).unwrap();
+    /// ```
+    pub fn start_asciicast<W: std::io::Write + 'static>(
+        &mut self,
+        output: W,
+        title: Option<&str>,
+    ) -> std::io::Result<()> {
+        let mut asciicast = AsciicastWriter::new(
+            Box::new(output) as Box<dyn std::io::Write>,
+            self.width,
+            self.height,
+            title,
+        )?;
+        if self.config.hide_cursor {
+            let mut buffer = vec![];
+            queue!(buffer, crossterm::cursor::Hide)?;
+            asciicast.write_output(&buffer)?;
+        }
+        self.asciicast = Some(asciicast);
+        self.request_full_draw();
+        Ok(())
+    }
+
+    /// Adds a marker at the current time of the asciicast recording, which players can jump to
+    pub fn add_asciicast_marker(&mut self, label: &str) {
+        if let Some(asciicast) = &mut self.asciicast {
+            if asciicast.write_marker(label).is_err() {
+                self.asciicast = None;
+            }
+        }
+    }
+
+    /// Stops the asciicast recording, flushing its output
+    pub fn stop_asciicast(&mut self) -> std::io::Result<()> {
+        match self.asciicast.take() {
+            Some(mut asciicast) => asciicast.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks whenever the frames are being recorded as an asciicast
+    pub fn is_recording_asciicast(&self) -> bool {
+        self.asciicast.is_some()
+    }
+
+    /// Encodes the output of a frame, wrapped into a synchronized update if enabled.
+    /// Nothing is written if the screen didn't change
+    fn encode_frame(&mut self, origin: u16) -> std::io::Result<Vec<u8>> {
+        let mut frame = vec![];
+        Self::write_frame(
+            &mut frame,
+            &self.screen,
+            &mut self.screen_last_frame,
+            origin,
+            self.color_depth,
+        )?;
+        if frame.is_empty() || !self.config.synchronized_update {
+            return Ok(frame);
+        }
+        // the terminal waits for the end of the frame before displaying it, to avoid tearing
+        let mut buffer = Vec::with_capacity(frame.len() + 16);
+        queue!(buffer, terminal::BeginSynchronizedUpdate)?;
+        buffer.extend_from_slice(&frame);
+        queue!(buffer, terminal::EndSynchronizedUpdate)?;
+        Ok(buffer)
+    }
+
+    /// Writes the escape sequences drawing the screen, only updating what changed since the last frame.
+    /// The colors are converted to the given color depth
+    fn write_frame<W: std::io::Write>(
+        output: &mut W,
+        screen: &Screen,
+        last_frame: &mut Screen,
+        origin: u16,
+        color_depth: ColorDepth,
+    ) -> std::io::Result<()> {
+        // position of the cursor in the screen, unknown until the first change is written
+        let mut cursor: Option<(i32, i32)> = None;
         let mut first = true;
-        let mut current_colors: (Color, Color) = (Color::Reset, Color::Reset);
-        let mut moving = false;
-        self.screen_last_frame.check_empty(); // refresh internal "empty" value of the last_frame screen
-        let mut skip_next = false;
+        let mut current_fg = Color::Reset;
+        let mut current_bg = Color::Reset;
+        let mut current_attr = Attributes::default();
+        last_frame.check_empty(); // refresh internal "empty" value of the last_frame screen
 
         // iterates through the screen memory and prints it on the output buffer
-        for y in 0..self.height as i32 {
-            for x in 0..self.width as i32 {
-                let pixel = self.screen.get_pxl(x, y).unwrap();
-                // we check if the screen has been modified at this coordinate or if the last_frame screen is empty
-                // if so, we write on the terminal normally, else we set a 'moving' flag
+        for y in 0..screen.get_height() as i32 {
+            // rows that haven't been modified since the last frame can't have changed
+            if !last_frame.is_empty() && !screen.is_row_dirty(y as u32) {
+                continue;
+            }
+            // a wide pixel in the last column doesn't cover the next row
+            let mut skip_next = false;
+            for x in 0..screen.get_width() as i32 {
+                let pixel = screen.get_pxl(x, y).unwrap();
                 if skip_next {
                     skip_next = false;
                     continue;
                 }
-                if let Some(char_width) = unicode_width::UnicodeWidthChar::width(pixel.chr) {
-                    if char_width > 1 {
-                        skip_next = true;
-                    }
+                if pixel.width() > 1 {
+                    skip_next = true;
                 }
-                if self.screen_last_frame.is_empty()
-                    || pixel != self.screen_last_frame.get_pxl(x, y).unwrap()
-                {
-                    if moving {
-                        // if the moving flag is set, we need to write a goto instruction first
-                        // this optimization minimize useless write on the screen
-                        // actually writing to the screen is very slow so it's a good compromise
-                        queue!(self.stdout, crossterm::cursor::MoveTo(x as u16, y as u16)).unwrap();
-                        moving = false;
-                    }
-                    // we check if the last color is the same as the current one.
-                    // if the color is the same, only print the character
-                    // the less we write on the output the faster we'll get
-                    // and additional characters for colors we already have set is
-                    // time consuming
-                    if current_colors != pixel.get_colors() || first {
-                        current_colors = pixel.get_colors();
-                        queue!(
-                            self.stdout,
-                            style::SetForegroundColor(pixel.fg),
-                            style::SetBackgroundColor(pixel.bg),
-                            style::Print(pixel.chr)
-                        )
-                        .unwrap();
-                        first = false;
-                    } else {
-                        queue!(self.stdout, style::Print(pixel.chr)).unwrap();
+                // we only write what has been modified since the last frame, unless the last frame is empty
+                if !last_frame.is_empty() && pixel == last_frame.get_pxl(x, y).unwrap() {
+                    continue;
+                }
+                if cursor != Some((x, y)) {
+                    // the cursor may be moved over unchanged pixels printed with the current style
+                    let style = (current_fg, current_bg, current_attr);
+                    Self::move_cursor(output, screen, cursor, (x, y), origin, style, color_depth)?;
+                }
+                let fg = color_depth.map(pixel.fg);
+                let bg = color_depth.map(pixel.bg);
+                // attributes can only be removed all at once, which also resets the colors
+                if current_attr != pixel.attr || first {
+                    current_attr = pixel.attr;
+                    current_fg = Color::Reset;
+                    current_bg = Color::Reset;
+                    queue!(output, style::SetAttribute(Attribute::Reset))?;
+                    if !pixel.attr.is_empty() {
+                        queue!(output, style::SetAttributes(pixel.attr))?;
                     }
-                } else {
-                    moving = true
+                    first = false;
+                }
+                // only the colors that changed are written, the less we write on the output the faster we'll get
+                if current_fg != fg {
+                    current_fg = fg;
+                    queue!(output, style::SetForegroundColor(fg))?;
+                }
+                if current_bg != bg {
+                    current_bg = bg;
+                    queue!(output, style::SetBackgroundColor(bg))?;
                 }
+                queue!(output, style::Print(pixel))?;
+                cursor = match pixel.width() {
+                    // the terminal may or may not move the cursor
+                    0 => None,
+                    width => Some((x + width as i32, y)),
+                };
             }
-            // at the end of each line, we write a newline character
-            // I believe that since we're on raw mode we need CR and LF even on unix terminals
-            if y < self.height as i32 - 1 {
-                queue!(self.stdout, style::Print("\r\n")).unwrap();
+        }
+        Ok(())
+    }
+
+    /// Moves the cursor to the given position of the screen with the shortest sequence:
+    /// an absolute move, line breaks, a relative move,
+    /// or printing again the unchanged pixels in between if they use the current style
+    fn move_cursor<W: std::io::Write>(
+        output: &mut W,
+        screen: &Screen,
+        cursor: Option<(i32, i32)>,
+        (x, y): (i32, i32),
+        origin: u16,
+        style: (Color, Color, Attributes),
+        color_depth: ColorDepth,
+    ) -> std::io::Result<()> {
+        let digits = |value: i32| value.to_string().len();
+        let absolute_cost = 4 + digits(y + origin as i32 + 1) + digits(x + 1);
+        let (line_breaks, from_x) = match cursor {
+            Some((cursor_x, cursor_y)) if cursor_y == y && cursor_x < x => (0, cursor_x),
+            // line breaks go back to the start of the line, wherever the cursor is
+            Some((_, cursor_y)) if cursor_y < y => ((y - cursor_y) as usize, 0),
+            _ => {
+                return queue!(
+                    output,
+                    crossterm::cursor::MoveTo(x as u16, y as u16 + origin)
+                );
             }
+        };
+        let distance = x - from_x;
+        let relative_cost = if distance > 0 {
+            3 + digits(distance)
+        } else {
+            0
+        };
+        // pixels that can be printed again without changing the style
+        let reprint: Option<String> = (from_x..x)
+            .map(|pixel_x| {
+                let pixel = screen.get_pxl(pixel_x, y).ok()?;
+                let colors = (color_depth.map(pixel.fg), color_depth.map(pixel.bg));
+                let same_style = (colors.0, colors.1, pixel.attr) == style;
+                (same_style && pixel.width() == 1).then(|| pixel.to_string())
+            })
+            .collect();
+        let horizontal_cost = match &reprint {
+            Some(pixels) if pixels.len() < relative_cost => pixels.len(),
+            _ => relative_cost,
+        };
+        if absolute_cost <= 2 * line_breaks + horizontal_cost {
+            return queue!(
+                output,
+                crossterm::cursor::MoveTo(x as u16, y as u16 + origin)
+            );
         }
-        // flush the buffer into user's terminal
-        self.stdout.flush().unwrap();
-        // store the frame for the next draw call
-        self.screen_last_frame = self.screen.clone();
+        for _ in 0..line_breaks {
+            // we're on raw mode, so we need both CR and LF even on unix terminals
+            queue!(output, style::Print("\r\n"))?;
+        }
+        match reprint {
+            Some(pixels) if pixels.len() < relative_cost => queue!(output, style::Print(pixels)),
+            _ if distance > 0 => queue!(output, crossterm::cursor::MoveRight(distance as u16)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Flattens the layers of a compositor into the screen, then draw the screen in the terminal
+    ///
+    /// The layers are drawn over the current content of the screen,
+    /// so you may want to [clear](#method.clear_screen) it first, or use a background layer.
+    ///
+    /// *see example* `layers`
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::compositor::{Compositor, Layer};
+    ///
+    /// let mut compositor = Compositor::new();
+    /// compositor.add_layer("map", Layer::new(map_screen));
+    /// compositor.add_layer("hud", Layer::new(hud_screen).with_z(1));
+    /// // ...
+    /// engine.clear_screen();
+    /// engine.draw_compositor(&compositor);
+    /// ```
+    pub fn draw_compositor(&mut self, compositor: &Compositor) {
+        compositor.flatten(&mut self.screen);
+        self.draw();
     }
 
     /// Ask the engine to redraw the entire screen on the next `draw` call
@@ -666,37 +1341,46 @@ impl ConsoleEngine {
         let mut captured_keyboard: Vec<KeyEvent> = vec![];
         let mut captured_mouse: Vec<MouseEvent> = vec![];
         let mut captured_resize: Vec<(u16, u16)> = vec![];
+        let mut captured_paste = String::new();
+        let mut captured_user: Vec<UserEvent> = vec![];
+        let mut captured_timers: Vec<u32> = vec![];
 
         // if there is time before next frame, poll keyboard and mouse events until next frame
+        let mut has_input = false;
         let mut elapsed_time = self.instant.elapsed();
-        while self.time_limit > elapsed_time {
-            let remaining_time = self.time_limit - elapsed_time;
-            if let Ok(has_event) = event::poll(std::time::Duration::from_millis(
-                (remaining_time.as_millis() % self.time_limit.as_millis()) as u64,
-            )) {
-                if has_event {
-                    if let Ok(current_event) = event::read() {
-                        match current_event {
-                            Event::Key(evt) => {
-                                captured_keyboard.push(evt);
-                            }
-                            Event::Mouse(evt) => {
-                                captured_mouse.push(evt);
-                            }
-                            Event::Resize(w, h) => {
-                                captured_resize.push((w, h));
-                            }
-                            Event::FocusGained => (),
-                            Event::FocusLost => (),
-                            Event::Paste(_) => (),
-                        };
+        loop {
+            // a timer firing wakes an on demand engine up, like an input would
+            while let Some(id) = self.next_timer() {
+                captured_timers.push(id);
+                has_input = true;
+            }
+            if self.is_frame_due(elapsed_time, has_input) {
+                break;
+            }
+            // errors reading the terminal are ignored, the frame still comes
+            if let Ok(Some(message)) = self.next_input(self.poll_timeout(elapsed_time, has_input)) {
+                has_input = true;
+                match message {
+                    Message::Terminal(Event::Key(evt)) => {
+                        captured_keyboard.push(evt);
                     }
-                }
+                    Message::Terminal(Event::Mouse(evt)) => {
+                        captured_mouse.push(self.translate_mouse(evt));
+                    }
+                    Message::Terminal(Event::Resize(w, h)) => {
+                        captured_resize.push((w, h));
+                    }
+                    Message::Terminal(Event::FocusGained) => self.focused = true,
+                    Message::Terminal(Event::FocusLost) => self.focused = false,
+                    Message::Terminal(Event::Paste(text)) => captured_paste.push_str(&text),
+                    Message::User(event) => captured_user.push(event),
+                    // read_input gives the errors of the input thread as errors
+                    Message::Error(_) => {}
+                };
             }
             elapsed_time = self.instant.elapsed();
         }
-        self.instant = std::time::Instant::now();
-        self.frame_count = self.frame_count.wrapping_add(1);
+        self.next_frame();
 
         // updates pressed / held / released states
         let held = utils::intersect(
@@ -708,16 +1392,390 @@ impl ConsoleEngine {
         self.keys_held = utils::union(&held, &self.keys_pressed);
         self.mouse_events = captured_mouse;
         self.resize_events = captured_resize;
+        self.pasted = captured_paste;
+        self.user_events = captured_user;
+        self.fired_timers = captured_timers;
     }
 
-    /// Poll the next ConsoleEngine Event
-    /// This function waits for the next event to occur,
-    /// from a user event like key press or mouse click to automatic events like frame change
-    ///
-    /// usage:
-    /// ```
-    /// use console_engine::events::Event;
-    /// // initializes a screen with a 10x10 screen and targetting 30 fps
+    /// Starts a new frame, measuring how long the previous one took
+    fn next_frame(&mut self) {
+        let now = std::time::Instant::now();
+        self.frame_stats.push_frame(now - self.instant);
+        self.instant = now;
+        self.frame_count = self.frame_count.wrapping_add(1);
+        self.redraw_requested = false;
+        if let Some(recorder) = &mut self.recorder {
+            if recorder.flush().is_err() {
+                self.recorder = None;
+            }
+        }
+        if self.replay.as_ref().is_some_and(Replay::is_finished) {
+            self.replay = None;
+        }
+    }
+
+    /// Checks whenever the next frame can start.
+    /// In on demand mode, a frame also needs some input or a redraw request
+    fn is_frame_due(&self, elapsed_time: std::time::Duration, has_input: bool) -> bool {
+        if let Some(replay) = &self.replay {
+            // during a replay, frames only depend on the recording
+            return replay.is_frame_done(self.frame_count)
+                && (replay.get_speed() == ReplaySpeed::Fast || elapsed_time >= self.time_limit);
+        }
+        elapsed_time >= self.time_limit
+            && (!self.config.on_demand || self.redraw_requested || has_input)
+    }
+
+    /// Get how long to wait for an input event, before checking again if a frame is due or a timer fired
+    fn poll_timeout(
+        &self,
+        elapsed_time: std::time::Duration,
+        has_input: bool,
+    ) -> std::time::Duration {
+        if let Some(replay) = &self.replay {
+            return if replay.get_speed() == ReplaySpeed::Fast
+                || !replay.is_frame_done(self.frame_count)
+            {
+                std::time::Duration::ZERO
+            } else {
+                self.time_limit.saturating_sub(elapsed_time)
+            };
+        }
+        let timeout = if self.config.on_demand && !self.redraw_requested && !has_input {
+            // nothing to draw, sleep until something happens
+            IDLE_TIMEOUT
+        } else {
+            self.time_limit.saturating_sub(elapsed_time)
+        };
+        match self.timers.next_deadline() {
+            Some(deadline) => {
+                timeout.min(deadline.saturating_duration_since(std::time::Instant::now()))
+            }
+            None => timeout,
+        }
+    }
+
+    /// Schedules a timer firing once, after the given delay
+    ///
+    /// When it fires, [poll](#method.poll) returns `Event::Timer(id)`,
+    /// and [timer_fired](#method.timer_fired) returns `true` for the frame following [wait_frame](#method.wait_frame).
+    /// Several timers can share the same id.
+    ///
+    /// usage:
+    /// ```
+    /// const HIDE_MESSAGE: u32 = 0;
+    ///
+    /// engine.set_timeout(std::time::Duration::from_secs(2), HIDE_MESSAGE);
+    /// loop {
+    ///     engine.wait_frame();
+    ///     if engine.timer_fired(HIDE_MESSAGE) {
+    ///         show_message = false;
+    ///     }
+    ///     // ...
+    /// }
+    /// ```
+    pub fn set_timeout(&mut self, delay: std::time::Duration, id: u32) -> TimerHandle {
+        self.timers.add(id, std::time::Instant::now() + delay, None)
+    }
+
+    /// Schedules a timer firing repeatedly, every `interval`
+    ///
+    /// The interval doesn't depend on the frame rate. If the engine falls behind,
+    /// the missed occurrences are skipped instead of firing all at once.
+    ///
+    /// usage:
+    /// ```
+    /// const TICK: u32 = 0;
+    ///
+    /// // moves the game forward 4 times per second, whatever the FPS is
+    /// let tick = engine.set_interval(std::time::Duration::from_millis(250), TICK);
+    /// loop {
+    ///     match engine.poll() {
+    ///         Event::Timer(TICK) => game.update(),
+    ///         Event::Key(key) if key.code == KeyCode::Char('p') => {
+    ///             // pause the game
+    ///             engine.cancel_timer(tick);
+    ///         }
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub fn set_interval(&mut self, interval: std::time::Duration, id: u32) -> TimerHandle {
+        assert!(
+            !interval.is_zero(),
+            "The interval needs to be greater than zero."
+        );
+        self.timers
+            .add(id, std::time::Instant::now() + interval, Some(interval))
+    }
+
+    /// Cancels a timer.
+    /// Returns `false` if the timer already fired or has already been cancelled
+    pub fn cancel_timer(&mut self, handle: TimerHandle) -> bool {
+        self.timers.cancel(handle)
+    }
+
+    /// Checks whenever a timer is still scheduled
+    pub fn is_timer_active(&self, handle: TimerHandle) -> bool {
+        self.timers.is_active(handle)
+    }
+
+    /// Checks whenever a timer with the given id fired during the last frame
+    ///
+    /// see [set_timeout](#method.set_timeout)
+    pub fn timer_fired(&self, id: u32) -> bool {
+        self.fired_timers.contains(&id)
+    }
+
+    /// Give the ids of the timers that fired during the last frame, in the order they fired
+    pub fn get_fired_timers(&self) -> &[u32] {
+        &self.fired_timers
+    }
+
+    /// Change the target frames per second
+    ///
+    /// usage:
+    /// ```
+    /// // slow down while the application is in background
+    /// if !engine.has_focus() {
+    ///     engine.set_target_fps(5);
+    /// }
+    /// ```
+    pub fn set_target_fps(&mut self, target_fps: u32) {
+        assert!(target_fps > 0, "Target FPS needs to be greater than zero.");
+        self.config.target_fps = target_fps;
+        self.time_limit = std::time::Duration::from_millis(1000 / target_fps as u64);
+    }
+
+    /// Get the target frames per second
+    pub fn get_target_fps(&self) -> u32 {
+        self.config.target_fps
+    }
+
+    /// Enable or disable the on demand mode
+    ///
+    /// In on demand mode, [wait_frame](#method.wait_frame) and [poll](#method.poll) don't produce frames on their own:
+    /// they sleep until some input arrives, the terminal is resized or [request_redraw](#method.request_redraw) is called.
+    /// The target FPS still limits how often frames occur.
+    /// It keeps idle applications from using the CPU.
+    ///
+    /// usage:
+    /// ```
+    /// engine.set_on_demand(true);
+    /// loop {
+    ///     match engine.poll() {
+    ///         Event::Frame => {
+    ///             // only happens when a redraw has been requested
+    ///             engine.draw();
+    ///         }
+    ///         Event::Key(key_event) => {
+    ///             // update the state, then ask for a new frame
+    ///             engine.request_redraw();
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn set_on_demand(&mut self, on_demand: bool) {
+        self.config.on_demand = on_demand;
+    }
+
+    /// Checks whenever the on demand mode is enabled
+    pub fn is_on_demand(&self) -> bool {
+        self.config.on_demand
+    }
+
+    /// Ask for a new frame, for engines in on demand mode
+    ///
+    /// see [set_on_demand](#method.set_on_demand)
+    pub fn request_redraw(&mut self) {
+        self.redraw_requested = true;
+    }
+
+    /// Waits up to the given timeout for the next terminal input or custom event,
+    /// taking the terminal input from the replay if any
+    fn next_input(&mut self, timeout: std::time::Duration) -> Result<Option<Message>, ErrorKind> {
+        if let Some(replay) = &mut self.replay {
+            if let Some(event) = replay.pop_terminal(self.frame_count) {
+                return Ok(Some(Message::Terminal(event)));
+            }
+            // the terminal is ignored during a replay, only custom events are received
+            let deadline = std::time::Instant::now() + timeout;
+            loop {
+                let timeout = deadline.saturating_duration_since(std::time::Instant::now());
+                match self.input.recv_timeout(timeout) {
+                    Some(Message::Terminal(_)) => continue,
+                    // the terminal is read from this thread again once the replay ends
+                    Some(Message::Error(_)) => self.input.stop(),
+                    message => return Ok(message),
+                }
+            }
+        }
+        let message = self.read_input(timeout)?;
+        if let Some(Message::Terminal(event)) = &message {
+            self.record(RecordedInput::Terminal(event.clone()));
+        }
+        Ok(message)
+    }
+
+    /// Takes the next timer that fired, from the replay if any
+    fn next_timer(&mut self) -> Option<u32> {
+        let id = match &mut self.replay {
+            Some(replay) => replay.pop_timer(self.frame_count),
+            None => self.timers.pop_due(std::time::Instant::now()),
+        }?;
+        self.record(RecordedInput::Timer(id));
+        Some(id)
+    }
+
+    /// Writes an input into the recording if any, stopping the recording if the output fails
+    fn record(&mut self, input: RecordedInput) {
+        if let Some(recorder) = &mut self.recorder {
+            if recorder.record(self.frame_count, input).is_err() {
+                self.recorder = None;
+            }
+        }
+    }
+
+    /// Waits up to the given timeout for the next terminal input or custom event
+    fn read_input(&mut self, timeout: std::time::Duration) -> Result<Option<Message>, ErrorKind> {
+        let message = if self.input.is_threaded() {
+            self.input.recv_timeout(timeout)
+        } else {
+            // without the input thread, custom events are only checked between two reads of the terminal input
+            match self.input.try_recv() {
+                Some(message) => Some(message),
+                None if self.backend.poll_event(timeout)? => {
+                    Some(Message::Terminal(self.backend.read_event()?))
+                }
+                None => self.input.try_recv(),
+            }
+        };
+        match message {
+            // the input thread has stopped, the terminal is read from this thread from now on
+            Some(Message::Error(error)) => {
+                self.input.stop();
+                Err(error)
+            }
+            message => Ok(message),
+        }
+    }
+
+    /// Get a sender to send custom events to the engine, from any thread
+    ///
+    /// Custom events wake the engine up immediately when the input thread is enabled,
+    /// see [EngineBuilder::input_thread](config/struct.EngineBuilder.html#method.input_thread).
+    /// Otherwise they are received once the engine stops waiting for terminal input, at the latest on the next frame.
+    ///
+    /// see [EventSender](input/struct.EventSender.html)
+    pub fn event_sender(&self) -> EventSender {
+        self.input.sender()
+    }
+
+    /// Give the custom events received during the last frame
+    ///
+    /// see [event_sender](#method.event_sender)
+    pub fn get_user_events(&self) -> &[UserEvent] {
+        &self.user_events
+    }
+
+    /// Starts recording the input received by the engine into the given output
+    ///
+    /// Every terminal event and timer received by [wait_frame](#method.wait_frame) or [poll](#method.poll) is written
+    /// as it arrives, and the output is flushed on each frame. The recording stops if the output fails.
+    ///
+    /// see [record](record/index.html)
+    ///
+    /// usage:
+    /// ```
+    /// // keep the session of the player, to reproduce their bug report
+    /// let file = std::fs::File::create("session.rec").unwrap();
+    /// engine.start_recording(std::io::BufWriter::new(file)).unwrap();
+    /// ```
+    pub fn start_recording<W: std::io::Write + 'static>(
+        &mut self,
+        output: W,
+    ) -> std::io::Result<()> {
+        self.recorder = Some(Recorder::new(Box::new(output), self.frame_count)?);
+        Ok(())
+    }
+
+    /// Stops the recording, flushing its output
+    pub fn stop_recording(&mut self) -> std::io::Result<()> {
+        match self.recorder.take() {
+            Some(mut recorder) => recorder.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks whenever the input is being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Replays a recording in place of the terminal
+    ///
+    /// Each recorded event is given back in the same frame it has been received in, counted from now,
+    /// and the timers of the engine are replaced by the recorded ones, so the application goes through the same states.
+    /// Once every event has been given back, the replay ends and the terminal input is used again.
+    ///
+    /// see [Recording](record/struct.Recording.html)
+    pub fn start_replay(&mut self, recording: Recording, speed: ReplaySpeed) {
+        self.replay = Some(Replay::new(recording, speed, self.frame_count));
+    }
+
+    /// Stops the replay, getting the input from the terminal again
+    pub fn stop_replay(&mut self) {
+        self.replay = None;
+    }
+
+    /// Checks whenever a recording is being replayed
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Get the time elapsed between the last two frames
+    ///
+    /// Use it to move things at the same speed, whatever the actual frame rate is.
+    /// See also [FixedTimestep](timing/struct.FixedTimestep.html)
+    ///
+    /// usage:
+    /// ```
+    /// engine.wait_frame();
+    /// position += speed * engine.get_delta_time().as_secs_f32();
+    /// ```
+    pub fn get_delta_time(&self) -> std::time::Duration {
+        self.frame_stats.get_delta_time()
+    }
+
+    /// Get the average frames per second over the last 60 frames
+    pub fn get_fps(&self) -> f64 {
+        self.frame_stats.get_fps()
+    }
+
+    /// Get the time spent in the last [draw](#method.draw) call
+    pub fn get_draw_time(&self) -> std::time::Duration {
+        self.frame_stats.get_draw_time()
+    }
+
+    /// Get the amount of bytes written to the terminal by the last [draw](#method.draw) call
+    pub fn get_draw_size(&self) -> usize {
+        self.frame_stats.get_draw_size()
+    }
+
+    /// Get the timing statistics of the last frames
+    pub fn get_frame_stats(&self) -> &FrameStats {
+        &self.frame_stats
+    }
+
+    /// Poll the next ConsoleEngine Event
+    /// This function waits for the next event to occur,
+    /// from a user event like key press or mouse click to automatic events like frame change
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::events::Event;
+    /// // initializes a screen with a 10x10 screen and targetting 30 fps
     /// let mut engine = console_engine::ConsoleEngine::init(10, 10, 30).unwrap();
     /// loop {
     ///     match engine.poll() {
@@ -733,40 +1791,134 @@ impl ConsoleEngine {
     /// ```
     #[cfg(feature = "event")]
     pub fn poll(&mut self) -> events::Event {
-        use std::time::Duration;
+        // errors reading the terminal are ignored, the next frame still comes
+        match self.next_event(|_| Ok::<(), std::convert::Infallible>(())) {
+            Ok(event) => event,
+            Err(never) => match never {},
+        }
+    }
 
+    /// Poll the next ConsoleEngine Event like [poll](#method.poll),
+    /// returning an error if the terminal can't be read instead of ignoring it
+    ///
+    /// usage:
+    /// ```
+    /// loop {
+    ///     match engine.try_poll() {
+    ///         Ok(Event::Frame) => {
+    ///             engine.try_draw()?;
+    ///         }
+    ///         Ok(_) => {}
+    ///         // the terminal has been closed
+    ///         Err(error) => break,
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "event")]
+    pub fn try_poll(&mut self) -> Result<events::Event, Error> {
+        self.next_event(|error| Err(Error::Io(error)))
+    }
+
+    /// Waits for the next event, giving the errors reading the terminal to `on_error`,
+    /// which decides whenever to return them or to keep waiting
+    #[cfg(feature = "event")]
+    fn next_event<E>(
+        &mut self,
+        mut on_error: impl FnMut(ErrorKind) -> Result<(), E>,
+    ) -> Result<events::Event, E> {
         let mut elapsed_time = self.instant.elapsed();
         // guarantees that this loop is running at least once
         loop {
-            let remaining_time = if self.time_limit > elapsed_time {
-                self.time_limit - elapsed_time
-            } else {
-                Duration::from_millis(0)
-            };
-            if let Ok(has_event) = event::poll(std::time::Duration::from_millis(
-                (remaining_time.as_millis() % self.time_limit.as_millis()) as u64,
-            )) {
-                if has_event {
-                    if let Ok(current_event) = event::read() {
-                        match current_event {
-                            Event::Key(evt) => return events::Event::Key(evt),
-                            Event::Mouse(evt) => return events::Event::Mouse(evt),
-                            Event::Resize(w, h) => return events::Event::Resize(w, h),
-                            Event::FocusGained => (),
-                            Event::FocusLost => (),
-                            Event::Paste(_) => (),
-                        };
-                    }
-                }
+            if let Some(id) = self.next_timer() {
+                return Ok(events::Event::Timer(id));
+            }
+            match self.next_input(self.poll_timeout(elapsed_time, false)) {
+                Ok(Some(message)) => return Ok(self.message_to_event(message)),
+                Ok(None) => {}
+                Err(error) => on_error(error)?,
             }
             elapsed_time = self.instant.elapsed();
-            if self.time_limit <= elapsed_time {
+            if self.is_frame_due(elapsed_time, false) {
                 break;
             }
         }
-        self.instant = std::time::Instant::now();
-        self.frame_count = self.frame_count.wrapping_add(1);
-        events::Event::Frame
+        self.next_frame();
+        Ok(events::Event::Frame)
+    }
+
+    /// Converts a message of the channel into the matching event, keeping track of the focus
+    #[cfg(feature = "event")]
+    fn message_to_event(&mut self, message: Message) -> events::Event {
+        match message {
+            Message::Terminal(Event::Key(evt)) => events::Event::Key(evt),
+            Message::Terminal(Event::Mouse(evt)) => events::Event::Mouse(self.translate_mouse(evt)),
+            Message::Terminal(Event::Resize(w, h)) => {
+                // the terminal may have been cleared, so a redraw is needed
+                self.redraw_requested = true;
+                events::Event::Resize(w, h)
+            }
+            Message::Terminal(Event::FocusGained) => {
+                self.focused = true;
+                events::Event::Focus(true)
+            }
+            Message::Terminal(Event::FocusLost) => {
+                self.focused = false;
+                events::Event::Focus(false)
+            }
+            Message::Terminal(Event::Paste(text)) => events::Event::Paste(text),
+            Message::User(event) => events::Event::User(event),
+            Message::Error(_) => {
+                unreachable!("read_input gives the errors of the input thread as errors")
+            }
+        }
+    }
+
+    /// Get a stream of events, to use the engine from an async runtime (tokio, smol, async-std...)
+    ///
+    /// The stream behaves like [poll](#method.poll): it yields the input as it arrives,
+    /// and a `Frame` event each time a frame passes, driven by an async timer.
+    /// The terminal input is read by the input thread, which is started if needed.
+    ///
+    /// see [EventStream](stream/struct.EventStream.html)
+    #[cfg(feature = "async")]
+    pub fn event_stream(&mut self) -> stream::EventStream<'_, B> {
+        if !self.input.is_threaded() {
+            if let Some(reader) = self.backend.event_reader() {
+                self.input.spawn(reader);
+            }
+        }
+        stream::EventStream::new(self)
+    }
+
+    /// Draw the screen into an async output, such as an async stdout
+    ///
+    /// Works like [draw](#method.draw), without blocking while the frame is written.
+    ///
+    /// usage:
+    /// ```
+    /// let mut stdout = async_std::io::stdout();
+    /// engine.print(0, 0, "Hello, world!");
+    /// engine.draw_async(&mut stdout).await?;
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn draw_async<W: futures_io::AsyncWrite + Unpin>(
+        &mut self,
+        output: &mut W,
+    ) -> std::io::Result<()> {
+        let draw_start = std::time::Instant::now();
+        let origin = self.inline_origin.unwrap_or(0);
+        let frame = self.encode_frame(origin)?;
+        if let Err(error) = stream::write_all(output, &frame).await {
+            // the output may have received part of the frame, the next one can't be a diff
+            self.request_full_draw();
+            return Err(error);
+        }
+        self.write_asciicast_frame(&frame, origin);
+        self.backend.present(&self.screen);
+        self.store_last_frame();
+        self.frame_stats.push_draw_time(draw_start.elapsed());
+        self.frame_stats.push_draw_size(frame.len());
+        Ok(())
     }
 
     /// Check and resize the terminal if needed.
@@ -783,15 +1935,41 @@ impl ConsoleEngine {
     ///     // do your stuff
     /// }
     /// ```
+    ///
+    /// Panics if the size of the terminal can't be read, see [try_check_resize](#method.try_check_resize)
     pub fn check_resize(&mut self) {
-        if crossterm::terminal::size().unwrap() != (self.width as u16, self.height as u16) {
+        self.try_check_resize().unwrap();
+    }
+
+    /// Check and resize the terminal if needed, like [check_resize](#method.check_resize),
+    /// returning an error instead of panicking if the terminal can't be reached
+    pub fn try_check_resize(&mut self) -> Result<(), Error> {
+        if let Some(origin) = self.inline_origin {
+            // inline engines keep their height and follow the width of the terminal
+            let (term_width, term_height) = self.backend.size()?;
+            if term_width as u32 != self.width || origin as u32 + self.height > term_height as u32 {
+                let height = self.height.min(term_height.max(1) as u32);
+                let origin = origin.min(term_height.saturating_sub(height as u16));
+                self.inline_origin = Some(origin);
+                self.resize(term_width as u32, height);
+                // the terminal may have moved the previous frame while reflowing, start over
+                execute!(
+                    self.backend,
+                    crossterm::cursor::MoveTo(0, origin),
+                    terminal::Clear(ClearType::FromCursorDown)
+                )?;
+            }
+            return Ok(());
+        }
+        let size = self.backend.size()?;
+        if size != (self.width as u16, self.height as u16) {
             // resize terminal
-            let size = crossterm::terminal::size().unwrap();
             let new_width = size.0 as u32;
             let new_height = size.1 as u32;
 
             self.resize(new_width, new_height);
         }
+        Ok(())
     }
 
     /// checks whenever a key is pressed (first frame held only)
@@ -945,6 +2123,33 @@ impl ConsoleEngine {
         None
     }
 
+    /// Give the text pasted in the terminal during the last frame
+    ///
+    /// Requires bracketed paste to be enabled, see [EngineBuilder](config/struct.EngineBuilder.html#method.bracketed_paste).
+    /// Otherwise the pasted text is received as key presses.
+    ///
+    /// usage:
+    /// ```
+    /// if let Some(text) = engine.get_paste() {
+    ///     // do something
+    /// }
+    /// ```
+    pub fn get_paste(&self) -> Option<&str> {
+        if self.pasted.is_empty() {
+            None
+        } else {
+            Some(&self.pasted)
+        }
+    }
+
+    /// Checks whenever the terminal has the focus
+    ///
+    /// Requires focus reporting to be enabled, see [EngineBuilder](config/struct.EngineBuilder.html#method.focus_reporting).
+    /// Otherwise the terminal is always considered focused.
+    pub fn has_focus(&self) -> bool {
+        self.focused
+    }
+
     /// Give the mouse's terminal coordinates if a button is held on the mouse
     ///
     /// usage:
@@ -1058,9 +2263,427 @@ impl ConsoleEngine {
     }
 }
 
-impl Drop for ConsoleEngine {
+impl<B: Backend> Drop for ConsoleEngine<B> {
     /// gracefully stop the engine when dropping it
     fn drop(&mut self) {
         self.end();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    use crate::backend::HeadlessBackend;
+    use crate::{pixel, ConsoleEngine};
+
+    /// Output that can still be read once given to the engine
+    #[derive(Clone, Default)]
+    pub(crate) struct SharedOutput(pub(crate) Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Engine drawing on a headless terminal of the given size, targeting 1000 FPS so frames don't slow the tests down
+    pub(crate) fn engine(width: u16, height: u16) -> ConsoleEngine<HeadlessBackend> {
+        ConsoleEngine::init_with_backend(
+            HeadlessBackend::new(width, height),
+            width as u32,
+            height as u32,
+            1000,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn focus_and_paste() {
+        use crossterm::event::Event;
+
+        let mut engine = engine(10, 3);
+        assert!(engine.has_focus());
+        engine.backend_mut().push_event(Event::FocusLost);
+        engine
+            .backend_mut()
+            .push_event(Event::Paste(String::from("abc")));
+        engine
+            .backend_mut()
+            .push_event(Event::Paste(String::from("def")));
+        engine.wait_frame();
+        assert!(!engine.has_focus());
+        assert_eq!(engine.get_paste(), Some("abcdef"));
+
+        engine.backend_mut().push_event(Event::FocusGained);
+        engine.wait_frame();
+        assert!(engine.has_focus());
+        assert_eq!(engine.get_paste(), None);
+    }
+
+    #[test]
+    fn timing() {
+        let mut engine = engine(10, 3);
+        assert_eq!(engine.get_fps(), 0.0);
+        engine.wait_frame();
+        engine.wait_frame();
+        // the target is 1000 FPS, so a frame lasts at least 1ms
+        assert!(engine.get_delta_time() >= std::time::Duration::from_millis(1));
+        assert!(engine.get_fps() > 0.0 && engine.get_fps() <= 1000.0);
+        assert_eq!(
+            engine.get_frame_stats().get_delta_time(),
+            engine.get_delta_time()
+        );
+    }
+
+    #[test]
+    fn attributes() {
+        use crate::{Attribute, Color};
+
+        let mut engine = engine(10, 1);
+        engine.print_fbg_attr(0, 0, "ab", Color::Red, Color::Reset, Attribute::Bold.into());
+        engine.draw();
+        let output = String::from_utf8_lossy(engine.backend().output()).to_string();
+        let bold = output.find("\x1b[1m").unwrap();
+        // colors are set after the attributes, since resetting attributes also resets colors
+        assert!(output[bold..].starts_with("\x1b[1m\x1b[38;5;9mab\x1b[0m"));
+        assert_eq!(
+            engine
+                .backend()
+                .last_frame()
+                .unwrap()
+                .get_pxl(1, 0)
+                .unwrap()
+                .attr,
+            Attribute::Bold.into()
+        );
+    }
+
+    #[test]
+    fn inline() {
+        let mut backend = HeadlessBackend::new(10, 5);
+        backend.set_cursor_position(3, 3);
+        let mut engine = ConsoleEngine::init_inline_with_backend(backend, 3, 1000).unwrap();
+        assert!(engine.is_inline());
+        assert_eq!((engine.get_width(), engine.get_height()), (10, 3));
+        let output = String::from_utf8_lossy(engine.backend().output()).to_string();
+        // no alternate screen, the terminal scrolls to make room below the cursor
+        assert!(!output.contains("\x1b[?1049h"));
+        assert!(output.starts_with("\r\n\r\n\r\n\x1b[3;1H\x1b[J"));
+
+        let start = engine.backend().output().len();
+        engine.print(0, 0, "ab");
+        engine.draw();
+        let output = String::from_utf8_lossy(&engine.backend().output()[start..]).to_string();
+        // the frame is drawn from the first row of the region
+        assert!(output.starts_with("\x1b[?2026h\x1b[3;1H"));
+        assert_eq!(output.matches("\r\n").count(), 2);
+
+        engine.backend_mut().push_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 4,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        });
+        engine.wait_frame();
+        assert_eq!(engine.get_mouse_press(MouseButton::Left), Some((4, 1)));
+    }
+
+    #[test]
+    fn config() {
+        let engine = ConsoleEngine::builder()
+            .fill_require(8, 4)
+            .mouse_capture(false)
+            .alternate_screen(false)
+            .raw_mode(false)
+            .focus_reporting(true)
+            .bracketed_paste(true)
+            .build_with_backend(HeadlessBackend::new(10, 5))
+            .unwrap();
+        assert_eq!((engine.get_width(), engine.get_height()), (10, 5));
+        assert!(!engine.backend().is_raw_mode_enabled());
+        let output = String::from_utf8_lossy(engine.backend().output()).to_string();
+        assert!(!output.contains("\x1b[?1049h"));
+        assert!(!output.contains("\x1b[?1000h"));
+        assert!(output.contains("\x1b[?25l"));
+        assert!(output.contains("\x1b[?1004h"));
+        assert!(output.contains("\x1b[?2004h"));
+
+        // the terminal can't be resized to the minimum size
+        assert!(matches!(
+            ConsoleEngine::builder()
+                .fill_require(20, 5)
+                .build_with_backend(HeadlessBackend::new(10, 5)),
+            Err(crate::Error::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "event")]
+    #[test]
+    fn poll() {
+        use crate::events::Event;
+
+        let mut engine = engine(10, 3);
+        engine.backend_mut().push_key(KeyCode::Enter);
+        assert!(matches!(engine.poll(), Event::Key(key) if key.code == KeyCode::Enter));
+        assert!(matches!(engine.poll(), Event::Frame));
+    }
+
+    #[cfg(feature = "event")]
+    #[test]
+    fn on_demand() {
+        use crate::events::Event;
+
+        let mut engine = ConsoleEngine::builder()
+            .size(10, 3)
+            .target_fps(1000)
+            .on_demand(true)
+            .build_with_backend(HeadlessBackend::new(10, 3))
+            .unwrap();
+        // the first frame is always drawn
+        assert!(matches!(engine.poll(), Event::Frame));
+
+        engine.backend_mut().push_key(KeyCode::Enter);
+        assert!(matches!(engine.poll(), Event::Key(key) if key.code == KeyCode::Enter));
+        engine.request_redraw();
+        assert!(matches!(engine.poll(), Event::Frame));
+
+        // wait_frame returns as soon as some input arrives
+        engine.backend_mut().push_key(KeyCode::Char('a'));
+        engine.wait_frame();
+        assert!(engine.is_key_pressed(KeyCode::Char('a')));
+
+        engine.set_target_fps(60);
+        assert_eq!(engine.get_target_fps(), 60);
+        assert_eq!(engine.get_config().target_fps, 60);
+        assert!(engine.is_on_demand());
+    }
+
+    #[cfg(feature = "event")]
+    #[test]
+    fn input_thread() {
+        use crate::events::Event;
+
+        let mut engine = ConsoleEngine::builder()
+            .size(10, 3)
+            .target_fps(1000)
+            .on_demand(true)
+            .input_thread(true)
+            .build_with_backend(HeadlessBackend::new(10, 3))
+            .unwrap();
+        assert!(matches!(engine.poll(), Event::Frame));
+
+        // terminal input goes through the input thread
+        engine.backend_mut().push_key(KeyCode::Enter);
+        assert!(matches!(engine.poll(), Event::Key(key) if key.code == KeyCode::Enter));
+
+        // custom events wake the engine up
+        let sender = engine.event_sender();
+        std::thread::spawn(move || sender.send(42u32).unwrap());
+        assert!(
+            matches!(engine.poll(), Event::User(event) if event.downcast_ref() == Some(&42u32))
+        );
+
+        engine.event_sender().send("hello").unwrap();
+        engine.wait_frame();
+        assert_eq!(engine.get_user_events().len(), 1);
+        assert_eq!(
+            engine.get_user_events()[0].downcast_ref::<&str>(),
+            Some(&"hello")
+        );
+
+        let sender = engine.event_sender();
+        drop(engine);
+        assert!(sender.send(0).is_err());
+    }
+
+    #[test]
+    fn user_events() {
+        // without the input thread, custom events are received between two reads of the input
+        let mut engine = engine(10, 3);
+        engine.event_sender().send(String::from("line")).unwrap();
+        engine.backend_mut().push_key(KeyCode::Char('a'));
+        engine.wait_frame();
+        assert!(engine.is_key_pressed(KeyCode::Char('a')));
+        assert_eq!(engine.get_user_events().len(), 1);
+    }
+
+    #[cfg(feature = "event")]
+    #[test]
+    fn timers() {
+        use crate::events::Event;
+        use std::time::Duration;
+
+        let mut engine = ConsoleEngine::builder()
+            .size(10, 3)
+            .target_fps(1000)
+            .on_demand(true)
+            .build_with_backend(HeadlessBackend::new(10, 3))
+            .unwrap();
+        assert!(matches!(engine.poll(), Event::Frame));
+
+        // a timer wakes an idle engine up
+        engine.set_timeout(Duration::from_millis(10), 1);
+        assert!(matches!(engine.poll(), Event::Timer(1)));
+
+        let interval = engine.set_interval(Duration::from_millis(5), 2);
+        engine.wait_frame();
+        assert!(engine.timer_fired(2));
+        assert!(!engine.timer_fired(1));
+        engine.wait_frame();
+        assert_eq!(engine.get_fired_timers(), &[2]);
+
+        assert!(engine.cancel_timer(interval));
+        assert!(!engine.is_timer_active(interval));
+        let cancelled = engine.set_timeout(Duration::from_millis(5), 3);
+        assert!(engine.cancel_timer(cancelled));
+        engine.request_redraw();
+        engine.wait_frame();
+        assert!(engine.get_fired_timers().is_empty());
+    }
+
+    #[test]
+    fn draw_encoding() {
+        use crate::Color;
+
+        let mut engine = engine(10, 3);
+        engine.draw();
+        let start = engine.backend().output().len();
+        engine.draw();
+        // nothing changed, nothing is written
+        assert_eq!(engine.backend().output().len(), start);
+        assert_eq!(engine.get_draw_size(), 0);
+
+        let frame = |engine: &mut ConsoleEngine<HeadlessBackend>| {
+            let start = engine.backend().output().len();
+            engine.draw();
+            let output = &engine.backend().output()[start..];
+            assert_eq!(output.len(), engine.get_draw_size());
+            String::from_utf8_lossy(output).to_string()
+        };
+        engine.set_pxl(5, 0, pixel::pxl('x'));
+        engine.set_pxl(5, 2, pixel::pxl('y'));
+        assert_eq!(
+            frame(&mut engine),
+            "\x1b[?2026h\x1b[1;6H\x1b[0mx\x1b[3;6Hy\x1b[?2026l"
+        );
+        // a short run of unchanged pixels is printed again instead of moving the cursor
+        engine.print(0, 1, "c d");
+        assert_eq!(
+            frame(&mut engine),
+            "\x1b[?2026h\x1b[2;1H\x1b[0mc d\x1b[?2026l"
+        );
+        // only the color that changed is written
+        engine.set_pxl(0, 1, pixel::pxl_fg('c', Color::Red));
+        engine.set_pxl(2, 1, pixel::pxl_fg('d', Color::Red));
+        assert_eq!(
+            frame(&mut engine),
+            "\x1b[?2026h\x1b[2;1H\x1b[0m\x1b[38;5;9mc\x1b[1Cd\x1b[?2026l"
+        );
+
+        // a wide pixel in the last column doesn't hide the first pixel of the next row drawn
+        engine.set_pxl(9, 0, pixel::pxl_str("汉"));
+        engine.set_pxl(0, 2, pixel::pxl('w'));
+        assert!(frame(&mut engine).ends_with("汉\r\n\r\nw\x1b[?2026l"));
+
+        let mut engine = ConsoleEngine::builder()
+            .synchronized_update(false)
+            .build_with_backend(HeadlessBackend::new(10, 3))
+            .unwrap();
+        engine.draw();
+        engine.set_pxl(1, 1, pixel::pxl('z'));
+        assert_eq!(frame(&mut engine), "\x1b[2;2H\x1b[0mz");
+    }
+
+    #[test]
+    fn color_depth() {
+        use crate::color::ColorDepth;
+        use crate::Color;
+
+        let mut backend = HeadlessBackend::new(10, 1);
+        backend.set_color_depth(ColorDepth::Ansi256);
+        let mut engine = ConsoleEngine::init_with_backend(backend, 10, 1, 30).unwrap();
+        assert_eq!(engine.get_color_depth(), ColorDepth::Ansi256);
+        let orange = Color::Rgb {
+            r: 255,
+            g: 135,
+            b: 0,
+        };
+        engine.print_fbg(0, 0, "a", orange, Color::Reset);
+        engine.draw();
+        let output = String::from_utf8_lossy(engine.backend().output()).to_string();
+        assert!(output.contains("\x1b[38;5;208ma"));
+        // the screen keeps its colors
+        assert_eq!(engine.get_pxl(0, 0).unwrap().fg, orange);
+
+        // every color is stripped, and the screen is drawn again
+        engine.set_color_depth(ColorDepth::NoColor);
+        let start = engine.backend().output().len();
+        engine.draw();
+        let output = String::from_utf8_lossy(&engine.backend().output()[start..]).to_string();
+        assert!(output.contains('a'));
+        assert!(!output.contains("\x1b[38;") && !output.contains("\x1b[48;"));
+
+        // the configuration overrides the backend
+        let engine = ConsoleEngine::builder()
+            .color_depth(ColorDepth::Ansi16)
+            .build_with_backend(HeadlessBackend::new(10, 1))
+            .unwrap();
+        assert_eq!(engine.get_color_depth(), ColorDepth::Ansi16);
+    }
+
+    #[cfg(feature = "event")]
+    #[test]
+    fn disconnected() {
+        use crate::events::Event;
+        use crate::Error;
+
+        let mut engine = engine(5, 2);
+        engine.backend_mut().set_disconnected(true);
+        engine.print(0, 0, "lost");
+        assert!(matches!(engine.try_draw(), Err(Error::Io(_))));
+        // part of the frame may have been written, the next one is drawn entirely
+        assert!(engine.screen_last_frame.is_empty());
+        assert!(engine.try_check_resize().is_err());
+        assert!(engine.try_poll().is_err());
+        // the non failing functions keep running
+        engine.wait_frame();
+        assert!(matches!(engine.poll(), Event::Frame));
+
+        // the engine recovers once the terminal is back, drawing the whole screen again
+        engine.backend_mut().set_disconnected(false);
+        engine.try_draw().unwrap();
+        assert_eq!(engine.backend().frames().len(), 1);
+        let output = String::from_utf8_lossy(engine.backend().output()).to_string();
+        assert!(output.contains("lost"));
+        assert!(engine.get_pxl(5, 0).is_err());
+
+        // stopping the engine doesn't panic either
+        engine.backend_mut().set_disconnected(true);
+        drop(engine);
+    }
+
+    #[cfg(feature = "event")]
+    #[test]
+    fn disconnected_threaded() {
+        let mut engine = ConsoleEngine::builder()
+            .size(5, 2)
+            .input_thread(true)
+            .build_with_backend(HeadlessBackend::new(5, 2))
+            .unwrap();
+        assert!(engine.input.is_threaded());
+        engine.backend_mut().set_disconnected(true);
+        // the input thread notices it within a few frames
+        assert!((0..10).any(|_| engine.try_poll().is_err()));
+        assert!(!engine.input.is_threaded());
+        // the terminal is read from the engine's thread, which keeps reporting the error
+        assert!(engine.try_poll().is_err());
+    }
+}
@@ -1,23 +1,134 @@
 //! Character and color management
 
-use crossterm::style::Color;
+use std::fmt;
+
+use crossterm::style::{Attributes, Color};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Maximum size in bytes of the characters that can follow the base character of a Pixel
+///
+/// With the length byte, [Combining] takes 24 bytes, keeping a Pixel at 40 bytes (12 bytes before grapheme clusters).
+/// That's enough for the longest common clusters, such as a family emoji (21 bytes after its first character).
+pub const COMBINING_CAPACITY: usize = 23;
+
+/// # Combining characters
+/// Characters following the base character of a grapheme cluster
+/// (combining accents, emoji modifiers, zero width joiner sequences, ...)
+///
+/// They are stored inline so Pixels can stay `Copy` and self-contained: pixels are copied between screens,
+/// views, layers and canvases, and frames are diffed by comparing pixels, which a side table of clusters
+/// (only used by multi-codepoint cells) would break by making each pixel depend on the screen holding its cluster.
+/// The cost is a larger Pixel, which only matters for the rows drawn since damage tracking skips the others.
+/// Sequences longer than [COMBINING_CAPACITY](constant.COMBINING_CAPACITY.html) bytes are not supported.
+#[derive(Clone, Copy, Eq, PartialEq, Default)]
+pub struct Combining {
+    len: u8,
+    bytes: [u8; COMBINING_CAPACITY],
+}
+
+impl Combining {
+    /// Stores the given characters, returns `None` if they don't fit
+    pub fn new(value: &str) -> Option<Combining> {
+        if value.len() > COMBINING_CAPACITY {
+            return None;
+        }
+        let mut combining = Combining {
+            len: value.len() as u8,
+            ..Default::default()
+        };
+        combining.bytes[..value.len()].copy_from_slice(value.as_bytes());
+        Some(combining)
+    }
+
+    /// Get the stored characters
+    pub fn as_str(&self) -> &str {
+        // the bytes always come from a valid &str
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+
+    /// Checks whenever there is no combining character
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl fmt::Debug for Combining {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
 
 /// # Pixel structure
-/// contains color data and character data
-#[derive(Clone, Eq, PartialEq, Copy)]
+/// contains color data, text attributes and character data
+#[derive(Clone, Eq, PartialEq, Copy, Debug)]
 pub struct Pixel {
     /// Background color of the Pixel
     pub bg: Color,
     /// Foreground color of the Pixel
     pub fg: Color,
+    /// Text attributes of the Pixel (bold, italic, underlined, ...)
+    pub attr: Attributes,
     /// Character of the Pixel
+    ///
+    /// If the Pixel holds a grapheme cluster, this is its first character
     pub chr: char,
+    /// Characters completing `chr` when the Pixel holds a grapheme cluster
+    pub combining: Combining,
 }
 impl Pixel {
     /// returns a tuple containing the background and foreground colors of a Pixel
     pub fn get_colors(&self) -> (Color, Color) {
         (self.fg, self.bg)
     }
+
+    /// Changes the character of the Pixel to the given grapheme cluster
+    ///
+    /// Only the first grapheme cluster of the string is used.
+    /// If the cluster is too long to be stored, only its first character is kept.
+    pub fn set_grapheme(&mut self, grapheme: &str) {
+        let grapheme = grapheme.graphemes(true).next().unwrap_or(" ");
+        let mut chars = grapheme.chars();
+        // unwrap here because graphemes are never empty
+        self.chr = chars.next().unwrap();
+        self.combining = Combining::new(chars.as_str()).unwrap_or_default();
+    }
+
+    /// Get the full grapheme cluster of the Pixel
+    pub fn grapheme(&self) -> String {
+        self.to_string()
+    }
+
+    /// Number of terminal cells needed to display the Pixel (0, 1 or 2)
+    pub fn width(&self) -> usize {
+        let width = unicode_width::UnicodeWidthChar::width(self.chr).unwrap_or(0);
+        if self.combining.is_empty() {
+            return width;
+        }
+        let combining = self.combining.as_str();
+        // emoji presentation selector, or a pair of regional indicators (flags)
+        let is_regional_indicator = |c: char| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c);
+        if combining.contains('\u{FE0F}')
+            || (is_regional_indicator(self.chr) && combining.chars().any(is_regional_indicator))
+        {
+            2
+        } else {
+            width
+        }
+    }
+
+    /// returns the Pixel with the given text attributes
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::pixel;
+    /// use console_engine::Attribute;
+    /// // ...
+    /// engine.set_pxl(0,0,pixel::pxl('X').with_attr(Attribute::Bold.into()));
+    /// ```
+    pub fn with_attr(mut self, attr: Attributes) -> Pixel {
+        self.attr = attr;
+        self
+    }
 }
 
 /// Generate a pixel using a character, a foreground and background color
@@ -30,7 +141,55 @@ impl Pixel {
 /// engine.set_pxl(0,0,pixel::pxl_fbg('X', Color::Blue, Color::White));
 /// ```
 pub fn pxl_fbg(value: char, fg: Color, bg: Color) -> Pixel {
-    Pixel { bg, fg, chr: value }
+    pxl_fbg_attr(value, fg, bg, Attributes::default())
+}
+
+/// Generate a pixel using a character, a foreground and background color and text attributes
+///
+/// usage:
+/// ```
+/// use console_engine::pixel;
+/// use console_engine::{Attribute, Color};
+/// // ...
+/// engine.set_pxl(0,0,pixel::pxl_fbg_attr('X', Color::Blue, Color::White, Attribute::Underlined.into()));
+/// ```
+pub fn pxl_fbg_attr(value: char, fg: Color, bg: Color, attr: Attributes) -> Pixel {
+    Pixel {
+        bg,
+        fg,
+        attr,
+        chr: value,
+        combining: Combining::default(),
+    }
+}
+
+/// Generate a pixel using a grapheme cluster, for characters that are made of several code points
+/// (emojis with modifiers, flags, letters with combining accents, ...)
+///
+/// usage:
+/// ```
+/// use console_engine::pixel;
+/// // ...
+/// engine.set_pxl(0,0,pixel::pxl_str("👍🏽"));
+/// ```
+pub fn pxl_str(value: &str) -> Pixel {
+    let mut pixel = pxl(' ');
+    pixel.set_grapheme(value);
+    pixel
+}
+
+/// Generate a pixel using a character and text attributes  
+/// Foreground and background colors are the terminal's default ones (`Color::Reset`).
+///
+/// usage:
+/// ```
+/// use console_engine::pixel;
+/// use console_engine::{Attribute, Attributes};
+/// // ...
+/// engine.set_pxl(0,0,pixel::pxl_attr('X', Attributes::from(Attribute::Bold) | Attribute::Italic));
+/// ```
+pub fn pxl_attr(value: char, attr: Attributes) -> Pixel {
+    pxl_fbg_attr(value, Color::Reset, Color::Reset, attr)
 }
 
 /// Generate a pixel using a character and a foreground color.  
@@ -44,11 +203,7 @@ pub fn pxl_fbg(value: char, fg: Color, bg: Color) -> Pixel {
 /// engine.set_pxl(0,0,pixel::pxl_fg('X', Color::Cyan));
 /// ```
 pub fn pxl_fg(value: char, fg: Color) -> Pixel {
-    Pixel {
-        fg,
-        bg: Color::Reset,
-        chr: value,
-    }
+    pxl_fbg(value, fg, Color::Reset)
 }
 /// Generate a pixel using a character and a background color.  
 /// Foreground color is always White.
@@ -61,11 +216,7 @@ pub fn pxl_fg(value: char, fg: Color) -> Pixel {
 /// engine.set_pxl(0,0,pixel::pxl_bg('X', Color::Magenta));
 /// ```
 pub fn pxl_bg(value: char, bg: Color) -> Pixel {
-    Pixel {
-        fg: Color::Reset,
-        bg,
-        chr: value,
-    }
+    pxl_fbg(value, Color::Reset, bg)
 }
 
 /// Generate a pixel using a character  
@@ -79,9 +230,52 @@ pub fn pxl_bg(value: char, bg: Color) -> Pixel {
 /// engine.set_pxl(0,0,pixel::pxl('X'));
 /// ```
 pub fn pxl(value: char) -> Pixel {
-    Pixel {
-        fg: Color::Reset,
-        bg: Color::Reset,
-        chr: value,
+    pxl_fbg(value, Color::Reset, Color::Reset)
+}
+
+impl fmt::Display for Pixel {
+    /// Displays the grapheme cluster of the Pixel, without its colors
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.chr, self.combining.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{pxl, pxl_str, Combining, Pixel, COMBINING_CAPACITY};
+
+    #[test]
+    fn graphemes() {
+        assert_eq!(pxl('a').grapheme(), "a");
+        assert_eq!(pxl('a').width(), 1);
+        assert_eq!(pxl('👍').width(), 2);
+        assert_eq!(pxl_str("a\u{301}").width(), 1);
+        assert_eq!(pxl_str("👍🏽").width(), 2);
+        assert_eq!(pxl_str("❤\u{fe0f}").width(), 2);
+        assert_eq!(pxl_str("🇫🇷").width(), 2);
+
+        // only the first grapheme is kept
+        let family = pxl_str("👨‍👩‍👧‍👦 and friends");
+        assert_eq!(family.grapheme(), "👨‍👩‍👧‍👦");
+        assert_eq!(family.to_string(), "👨‍👩‍👧‍👦");
+        assert_eq!(pxl_str("").chr, ' ');
+    }
+
+    #[test]
+    fn combining() {
+        assert!(Combining::default().is_empty());
+        assert_eq!(Combining::new("\u{301}").unwrap().as_str(), "\u{301}");
+        assert!(Combining::new(&"a".repeat(COMBINING_CAPACITY + 1)).is_none());
+
+        // clusters too long to be stored only keep their first character
+        let long = format!("a{}", "\u{301}".repeat(COMBINING_CAPACITY));
+        assert_eq!(pxl_str(&long).grapheme(), "a");
+    }
+
+    #[test]
+    fn size() {
+        // every cell of every screen has this size, think twice before growing it
+        assert_eq!(std::mem::size_of::<Combining>(), 24);
+        assert_eq!(std::mem::size_of::<Pixel>(), 40);
     }
 }
@@ -0,0 +1,574 @@
+//! Recording of the input received by an engine, and its replay
+//!
+//! While recording, every terminal event and timer received by `wait_frame` or `poll` is written
+//! with the frame it was received in and the time elapsed, both counted from the start of the recording.
+//! A [Recording] can then be replayed into an engine in place of the terminal: the events are
+//! given back in the same frames, so the application goes through exactly the same states.
+//!
+//! Recordings are stored as text, one event per line:
+//! ```text
+//! console_engine-recording 1
+//! 0 120533 key char:113 0 press 0
+//! 3 250127 mouse down:left 4 2 0
+//! 7 400012 timer 1
+//! ```
+//!
+//! Custom events sent with an [EventSender](../input/struct.EventSender.html) can't be recorded,
+//! they keep being received normally during a replay.
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+
+/// First line of a recording
+const HEADER: &str = "console_engine-recording 1";
+
+/// Input received by an engine
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecordedInput {
+    /// An event read from the terminal
+    Terminal(Event),
+    /// A timer fired, see [ConsoleEngine::set_timeout](../struct.ConsoleEngine.html#method.set_timeout)
+    Timer(u32),
+}
+
+/// An input, with the moment it has been received
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedEvent {
+    /// Frame the input has been received in, counted from the start of the recording
+    pub frame: usize,
+    /// Time elapsed since the recording started
+    pub time: Duration,
+    /// The input itself
+    pub input: RecordedInput,
+}
+
+impl RecordedEvent {
+    /// Converts the event into a line of a recording.
+    ///
+    /// Returns `None` for media and modifier keys, which can't be recorded
+    pub fn to_line(&self) -> Option<String> {
+        let input = match &self.input {
+            RecordedInput::Terminal(Event::Key(key)) => format!(
+                "key {} {} {} {}",
+                key_code_to_str(key.code)?,
+                key.modifiers.bits(),
+                match key.kind {
+                    KeyEventKind::Press => "press",
+                    KeyEventKind::Repeat => "repeat",
+                    KeyEventKind::Release => "release",
+                },
+                key.state.bits()
+            ),
+            RecordedInput::Terminal(Event::Mouse(mouse)) => format!(
+                "mouse {} {} {} {}",
+                mouse_kind_to_str(mouse.kind),
+                mouse.column,
+                mouse.row,
+                mouse.modifiers.bits()
+            ),
+            RecordedInput::Terminal(Event::Resize(width, height)) => {
+                format!("resize {} {}", width, height)
+            }
+            RecordedInput::Terminal(Event::FocusGained) => String::from("focus 1"),
+            RecordedInput::Terminal(Event::FocusLost) => String::from("focus 0"),
+            RecordedInput::Terminal(Event::Paste(text)) => {
+                // stored as hexadecimal, so the text can span several lines
+                let hex: String = text.bytes().map(|byte| format!("{:02x}", byte)).collect();
+                format!("paste {}", hex)
+            }
+            RecordedInput::Timer(id) => format!("timer {}", id),
+        };
+        Some(format!(
+            "{} {} {}",
+            self.frame,
+            self.time.as_micros(),
+            input
+        ))
+    }
+
+    /// Parses a line of a recording
+    pub fn parse_line(line: &str) -> io::Result<Self> {
+        let mut fields = line.split(' ');
+        let mut next = || fields.next().ok_or_else(|| invalid("missing field"));
+        let frame = parse_number(next()?)?;
+        let time = Duration::from_micros(parse_number(next()?)?);
+        let input = match next()? {
+            "key" => {
+                let code = key_code_from_str(next()?)?;
+                let modifiers = KeyModifiers::from_bits_truncate(parse_number(next()?)?);
+                let kind = match next()? {
+                    "press" => KeyEventKind::Press,
+                    "repeat" => KeyEventKind::Repeat,
+                    "release" => KeyEventKind::Release,
+                    kind => return Err(invalid(&format!("unknown key kind '{}'", kind))),
+                };
+                let state = KeyEventState::from_bits_truncate(parse_number(next()?)?);
+                RecordedInput::Terminal(Event::Key(KeyEvent {
+                    code,
+                    modifiers,
+                    kind,
+                    state,
+                }))
+            }
+            "mouse" => RecordedInput::Terminal(Event::Mouse(MouseEvent {
+                kind: mouse_kind_from_str(next()?)?,
+                column: parse_number(next()?)?,
+                row: parse_number(next()?)?,
+                modifiers: KeyModifiers::from_bits_truncate(parse_number(next()?)?),
+            })),
+            "resize" => RecordedInput::Terminal(Event::Resize(
+                parse_number(next()?)?,
+                parse_number(next()?)?,
+            )),
+            "focus" => match next()? {
+                "1" => RecordedInput::Terminal(Event::FocusGained),
+                "0" => RecordedInput::Terminal(Event::FocusLost),
+                focus => return Err(invalid(&format!("invalid focus '{}'", focus))),
+            },
+            "paste" => {
+                let hex = next()?;
+                if hex.len() % 2 != 0 {
+                    return Err(invalid("invalid pasted text"));
+                }
+                let bytes = (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(hex.get(i..i + 2).unwrap_or(""), 16))
+                    .collect::<Result<Vec<u8>, _>>()
+                    .map_err(|_| invalid("invalid pasted text"))?;
+                let text = String::from_utf8(bytes).map_err(|_| invalid("invalid pasted text"))?;
+                RecordedInput::Terminal(Event::Paste(text))
+            }
+            "timer" => RecordedInput::Timer(parse_number(next()?)?),
+            event => return Err(invalid(&format!("unknown event '{}'", event))),
+        };
+        if fields.next().is_some() {
+            return Err(invalid("too many fields"));
+        }
+        Ok(Self { frame, time, input })
+    }
+}
+
+/// A list of recorded events, in the order they have been received
+///
+/// usage:
+/// ```
+/// use console_engine::record::{Recording, ReplaySpeed};
+///
+/// // replays a session recorded with engine.start_recording(...)
+/// let recording = Recording::load("session.rec").unwrap();
+/// engine.start_replay(recording, ReplaySpeed::Fast);
+/// while engine.is_replaying() {
+///     engine.wait_frame();
+///     game.update(&engine);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Recording {
+    events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    /// Creates an empty recording
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an event at the end of the recording
+    pub fn push(&mut self, event: RecordedEvent) {
+        self.events.push(event);
+    }
+
+    /// Get the recorded events
+    pub fn get_events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    /// Reads a recording from a file
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read_from(io::BufReader::new(std::fs::File::open(path)?))
+    }
+
+    /// Reads a recording from any buffered reader
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        match lines.next() {
+            Some(Ok(header)) if header.trim_end() == HEADER => {}
+            Some(Err(error)) => return Err(error),
+            _ => return Err(invalid("not a console_engine recording")),
+        }
+        let mut recording = Self::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            let event = RecordedEvent::parse_line(line).map_err(|error| {
+                // the header is the first line
+                invalid(&format!("line {}: {}", index + 2, error))
+            })?;
+            recording.push(event);
+        }
+        Ok(recording)
+    }
+
+    /// Writes the recording into a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_to(&mut file)?;
+        file.flush()
+    }
+
+    /// Writes the recording into any output
+    pub fn write_to<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{}", HEADER)?;
+        for line in self.events.iter().filter_map(RecordedEvent::to_line) {
+            writeln!(output, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// How fast a recording is replayed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplaySpeed {
+    /// Frames keep happening at the target FPS of the engine
+    TargetFps,
+    /// Frames happen as fast as possible, for tests
+    Fast,
+}
+
+/// Writes the input of an engine as it is received
+pub(crate) struct Recorder {
+    output: Box<dyn Write>,
+    start: Instant,
+    start_frame: usize,
+}
+
+impl Recorder {
+    pub(crate) fn new(mut output: Box<dyn Write>, start_frame: usize) -> io::Result<Self> {
+        writeln!(output, "{}", HEADER)?;
+        Ok(Self {
+            output,
+            start: Instant::now(),
+            start_frame,
+        })
+    }
+
+    /// Writes an input received during the given frame
+    pub(crate) fn record(&mut self, frame: usize, input: RecordedInput) -> io::Result<()> {
+        let event = RecordedEvent {
+            frame: frame.wrapping_sub(self.start_frame),
+            time: self.start.elapsed(),
+            input,
+        };
+        match event.to_line() {
+            Some(line) => writeln!(self.output, "{}", line),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+
+/// Recorded events waiting to be given back to an engine
+pub(crate) struct Replay {
+    events: VecDeque<RecordedEvent>,
+    speed: ReplaySpeed,
+    start_frame: usize,
+}
+
+impl Replay {
+    pub(crate) fn new(recording: Recording, speed: ReplaySpeed, start_frame: usize) -> Self {
+        Self {
+            events: recording.events.into(),
+            speed,
+            start_frame,
+        }
+    }
+
+    pub(crate) fn get_speed(&self) -> ReplaySpeed {
+        self.speed
+    }
+
+    /// Checks whenever every event of the given frame has been given back
+    pub(crate) fn is_frame_done(&self, frame: usize) -> bool {
+        let frame = frame.wrapping_sub(self.start_frame);
+        self.events
+            .front()
+            .map_or(true, |event| event.frame > frame)
+    }
+
+    /// Checks whenever every event has been given back
+    pub(crate) fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Takes the next terminal event of the given frame, unless a timer comes first
+    pub(crate) fn pop_terminal(&mut self, frame: usize) -> Option<Event> {
+        let frame = frame.wrapping_sub(self.start_frame);
+        match self.events.front() {
+            Some(RecordedEvent {
+                frame: event_frame,
+                input: RecordedInput::Terminal(_),
+                ..
+            }) if *event_frame <= frame => match self.events.pop_front()?.input {
+                RecordedInput::Terminal(event) => Some(event),
+                RecordedInput::Timer(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Takes the next timer of the given frame, unless a terminal event comes first
+    pub(crate) fn pop_timer(&mut self, frame: usize) -> Option<u32> {
+        let frame = frame.wrapping_sub(self.start_frame);
+        match self.events.front() {
+            Some(RecordedEvent {
+                frame: event_frame,
+                input: RecordedInput::Timer(id),
+                ..
+            }) if *event_frame <= frame => {
+                let id = *id;
+                self.events.pop_front();
+                Some(id)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn parse_number<T: std::str::FromStr>(field: &str) -> io::Result<T> {
+    field
+        .parse()
+        .map_err(|_| invalid(&format!("invalid number '{}'", field)))
+}
+
+fn key_code_to_str(code: KeyCode) -> Option<String> {
+    Some(match code {
+        KeyCode::Char(chr) => format!("char:{}", chr as u32),
+        KeyCode::F(number) => format!("f:{}", number),
+        KeyCode::Backspace => String::from("backspace"),
+        KeyCode::Enter => String::from("enter"),
+        KeyCode::Left => String::from("left"),
+        KeyCode::Right => String::from("right"),
+        KeyCode::Up => String::from("up"),
+        KeyCode::Down => String::from("down"),
+        KeyCode::Home => String::from("home"),
+        KeyCode::End => String::from("end"),
+        KeyCode::PageUp => String::from("pageup"),
+        KeyCode::PageDown => String::from("pagedown"),
+        KeyCode::Tab => String::from("tab"),
+        KeyCode::BackTab => String::from("backtab"),
+        KeyCode::Delete => String::from("delete"),
+        KeyCode::Insert => String::from("insert"),
+        KeyCode::Null => String::from("null"),
+        KeyCode::Esc => String::from("esc"),
+        KeyCode::CapsLock => String::from("capslock"),
+        KeyCode::ScrollLock => String::from("scrolllock"),
+        KeyCode::NumLock => String::from("numlock"),
+        KeyCode::PrintScreen => String::from("printscreen"),
+        KeyCode::Pause => String::from("pause"),
+        KeyCode::Menu => String::from("menu"),
+        KeyCode::KeypadBegin => String::from("keypadbegin"),
+        // only reported with keyboard enhancements, which the engine doesn't enable
+        KeyCode::Media(_) | KeyCode::Modifier(_) => return None,
+    })
+}
+
+fn key_code_from_str(code: &str) -> io::Result<KeyCode> {
+    if let Some(chr) = code.strip_prefix("char:") {
+        return char::from_u32(parse_number(chr)?)
+            .map(KeyCode::Char)
+            .ok_or_else(|| invalid(&format!("invalid character '{}'", chr)));
+    }
+    if let Some(number) = code.strip_prefix("f:") {
+        return Ok(KeyCode::F(parse_number(number)?));
+    }
+    Ok(match code {
+        "backspace" => KeyCode::Backspace,
+        "enter" => KeyCode::Enter,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "null" => KeyCode::Null,
+        "esc" => KeyCode::Esc,
+        "capslock" => KeyCode::CapsLock,
+        "scrolllock" => KeyCode::ScrollLock,
+        "numlock" => KeyCode::NumLock,
+        "printscreen" => KeyCode::PrintScreen,
+        "pause" => KeyCode::Pause,
+        "menu" => KeyCode::Menu,
+        "keypadbegin" => KeyCode::KeypadBegin,
+        _ => return Err(invalid(&format!("unknown key '{}'", code))),
+    })
+}
+
+fn mouse_kind_to_str(kind: MouseEventKind) -> String {
+    let button = |button| match button {
+        MouseButton::Left => "left",
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+    };
+    match kind {
+        MouseEventKind::Down(btn) => format!("down:{}", button(btn)),
+        MouseEventKind::Up(btn) => format!("up:{}", button(btn)),
+        MouseEventKind::Drag(btn) => format!("drag:{}", button(btn)),
+        MouseEventKind::Moved => String::from("moved"),
+        MouseEventKind::ScrollDown => String::from("scrolldown"),
+        MouseEventKind::ScrollUp => String::from("scrollup"),
+    }
+}
+
+fn mouse_kind_from_str(kind: &str) -> io::Result<MouseEventKind> {
+    let (name, button) = match kind.split_once(':') {
+        Some((name, button)) => {
+            let button = match button {
+                "left" => MouseButton::Left,
+                "right" => MouseButton::Right,
+                "middle" => MouseButton::Middle,
+                _ => return Err(invalid(&format!("unknown mouse button '{}'", button))),
+            };
+            (name, Some(button))
+        }
+        None => (kind, None),
+    };
+    Ok(match (name, button) {
+        ("down", Some(btn)) => MouseEventKind::Down(btn),
+        ("up", Some(btn)) => MouseEventKind::Up(btn),
+        ("drag", Some(btn)) => MouseEventKind::Drag(btn),
+        ("moved", None) => MouseEventKind::Moved,
+        ("scrolldown", None) => MouseEventKind::ScrollDown,
+        ("scrollup", None) => MouseEventKind::ScrollUp,
+        _ => return Err(invalid(&format!("unknown mouse event '{}'", kind))),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    use super::{RecordedEvent, RecordedInput, Recording, ReplaySpeed};
+    use crate::backend::HeadlessBackend;
+    use crate::test::{engine, SharedOutput};
+    use crate::ConsoleEngine;
+
+    #[test]
+    fn round_trip() {
+        let inputs = vec![
+            RecordedInput::Terminal(Event::Key(KeyEvent::new(
+                KeyCode::Char(' '),
+                KeyModifiers::SHIFT,
+            ))),
+            RecordedInput::Terminal(Event::Key(KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE))),
+            RecordedInput::Terminal(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Right),
+                column: 4,
+                row: 2,
+                modifiers: KeyModifiers::CONTROL,
+            })),
+            RecordedInput::Terminal(Event::Resize(80, 24)),
+            RecordedInput::Terminal(Event::FocusLost),
+            RecordedInput::Terminal(Event::Paste(String::from("two\nlines é"))),
+            RecordedInput::Timer(3),
+        ];
+        let mut recording = Recording::new();
+        for (frame, input) in inputs.into_iter().enumerate() {
+            recording.push(RecordedEvent {
+                frame,
+                time: Duration::from_micros(frame as u64 * 1000),
+                input,
+            });
+        }
+        let mut text = vec![];
+        recording.write_to(&mut text).unwrap();
+        assert_eq!(Recording::read_from(&text[..]).unwrap(), recording);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Recording::read_from(&b"0 0 timer 1\n"[..]).is_err());
+        let error =
+            Recording::read_from(&b"console_engine-recording 1\n0 0 timer 1\n0 0 jump\n"[..])
+                .unwrap_err();
+        assert!(error.to_string().starts_with("line 3"));
+        assert!(RecordedEvent::parse_line("1 2 resize 10").is_err());
+        assert!(RecordedEvent::parse_line("1 2 focus 1 1").is_err());
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let output = SharedOutput::default();
+        let mut engine = engine(10, 3);
+        engine.wait_frame();
+        engine.start_recording(output.clone()).unwrap();
+        engine.backend_mut().push_key(KeyCode::Char('a'));
+        engine.wait_frame();
+        engine.wait_frame();
+        engine.set_timeout(Duration::ZERO, 7);
+        engine.backend_mut().push_key(KeyCode::Char('b'));
+        engine.wait_frame();
+        assert!(engine.timer_fired(7) && engine.is_key_pressed(KeyCode::Char('b')));
+        engine.stop_recording().unwrap();
+        assert!(!engine.is_recording());
+
+        let recording = Recording::read_from(&output.0.lock().unwrap()[..]).unwrap();
+        let frames: Vec<usize> = recording.get_events().iter().map(|e| e.frame).collect();
+        assert_eq!(frames, vec![0, 2, 2]);
+
+        // the replay starts from any frame, ignoring the terminal
+        let mut engine = ConsoleEngine::builder()
+            .size(10, 3)
+            .target_fps(1)
+            .on_demand(true)
+            .build_with_backend(HeadlessBackend::new(10, 3))
+            .unwrap();
+        engine.set_interval(Duration::from_millis(1), 8);
+        engine.start_replay(recording, ReplaySpeed::Fast);
+        engine.backend_mut().push_key(KeyCode::Char('z'));
+        let mut replayed = vec![];
+        while engine.is_replaying() {
+            engine.wait_frame();
+            let keys = [KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Char('z')];
+            let pressed: Vec<KeyCode> = keys
+                .into_iter()
+                .filter(|key| engine.is_key_pressed(*key))
+                .collect();
+            replayed.push((pressed, engine.get_fired_timers().to_vec()));
+        }
+        assert_eq!(
+            replayed,
+            vec![
+                (vec![KeyCode::Char('a')], vec![]),
+                (vec![], vec![]),
+                (vec![KeyCode::Char('b')], vec![7]),
+            ]
+        );
+    }
+}
@@ -1,8 +1,14 @@
 //! Standalone screens
 
+use std::fmt;
+
+use crate::color::{Gradient, GradientDirection};
+use crate::error::Error;
+use crate::layout::Rect;
 use crate::rect_style::BorderStyle;
+use crate::view::ScreenView;
 
-use super::crossterm::style::Color;
+use super::crossterm::style::{Attribute, Attributes, Color};
 use super::crossterm::{execute, style};
 use super::pixel;
 use super::pixel::Pixel;
@@ -18,6 +24,7 @@ pub struct Screen {
     height: u32,
     screen: Vec<Pixel>,
     empty: bool,
+    dirty_rows: Vec<bool>,
 }
 
 /// # Basic Usage :
@@ -63,6 +70,7 @@ impl Screen {
             height,
             screen: vec![pixel; (width * height) as usize],
             empty: false,
+            dirty_rows: vec![true; height as usize],
         }
     }
 
@@ -75,6 +83,7 @@ impl Screen {
             height,
             screen: vec,
             empty: false,
+            dirty_rows: vec![true; height as usize],
         }
     }
 
@@ -98,6 +107,25 @@ impl Screen {
         self.height
     }
 
+    /// Get the area covered by the screen, to be split with a [Layout](../layout/struct.Layout.html)
+    pub fn get_area(&self) -> Rect {
+        Rect::new(0, 0, self.width, self.height)
+    }
+
+    /// Creates a view on a region of the screen
+    ///
+    /// Everything drawn through the view uses coordinates relative to the region, and is clipped to it
+    ///
+    /// usage:
+    /// ```
+    /// let mut panel = screen.view(10, 2, 20, 5);
+    /// // prints at [11, 3] on the screen, cut after 20 characters
+    /// panel.print(1, 1, "Hello, world!");
+    /// ```
+    pub fn view(&mut self, x: i32, y: i32, width: u32, height: u32) -> ScreenView<'_> {
+        ScreenView::new(self, x, y, width, height)
+    }
+
     /// Reset the screen to a blank state
     pub fn clear(&mut self) {
         self.fill(pixel::pxl(' '));
@@ -106,7 +134,45 @@ impl Screen {
     /// Fill the entire screen to the given pixel
     pub fn fill(&mut self, pixel: Pixel) {
         self.empty = pixel.chr == '\u{0}';
-        self.screen = vec![pixel; (self.width * self.height) as usize];
+        let rows = self.screen.chunks_mut(self.width.max(1) as usize);
+        for (row, dirty) in rows.zip(self.dirty_rows.iter_mut()) {
+            // rows already filled with the pixel stay clean
+            if row.iter().any(|pxl| *pxl != pixel) {
+                row.fill(pixel);
+                *dirty = true;
+            }
+        }
+    }
+
+    /// Checks whenever the given row has been modified since the last call to [clear_dirty](#method.clear_dirty)
+    ///
+    /// A new screen has all of its rows dirty
+    pub fn is_row_dirty(&self, y: u32) -> bool {
+        self.dirty_rows.get(y as usize).copied().unwrap_or(false)
+    }
+
+    /// Marks every row of the screen as modified
+    pub fn mark_dirty(&mut self) {
+        self.dirty_rows.fill(true);
+    }
+
+    /// Marks every row of the screen as unmodified
+    pub fn clear_dirty(&mut self) {
+        self.dirty_rows.fill(false);
+    }
+
+    /// Copies the dirty rows of a screen of the same size
+    pub(crate) fn copy_dirty_rows(&mut self, source: &Screen) {
+        let width = self.width as usize;
+        for (y, _) in source
+            .dirty_rows
+            .iter()
+            .enumerate()
+            .filter(|(_, dirty)| **dirty)
+        {
+            let row = y * width..(y + 1) * width;
+            self.screen[row.clone()].copy_from_slice(&source.screen[row]);
+        }
     }
 
     /// checks whenever the screen is full of "zero" characters
@@ -150,67 +216,54 @@ impl Screen {
     /// screen.print(0, 0, "Hello, world!", Color::Blue, Color::White);
     /// ```
     pub fn print_fbg(&mut self, x: i32, y: i32, string: &str, fg: Color, bg: Color) {
-        if x < self.width as i32 && y < self.height as i32 {
-            let mut string = string;
-            let mut y = y;
-            // if the cursor is above the screen
-            if y < 0 {
-                // cuts the string per \n character
-                // until the cursor enters the screen
-                let mut delta_y = -y;
-                while delta_y > 0 {
-                    if let Some(pos) = string.find('\n') {
-                        string = &string[pos + 1..];
-                        delta_y -= 1;
-                    } else {
-                        // no more rows
-                        string = "";
-                        break;
-                    }
-                }
-                y = 0;
-            }
-            // get screen index, initializes a counter
-            let mut pos = self.coord_to_index(std::cmp::max(0, x), y);
-            let delta_x = if x < 0 { -x } else { 0 };
-            // set an ignore count to skip a certain number of chars if the text is hidden on the left
-            let mut ignore_count = delta_x;
-            let mut origin_row = pos / self.get_width() as usize;
-            // place each characters one by one. Stops before overflowing
-            for str_chr in string.chars() {
-                let mut chr = str_chr;
-                // process carret return and new line characters
-                if chr == '\n' {
-                    y += 1;
-                    origin_row += 1;
-                    ignore_count = delta_x;
-                    if y >= self.height as i32 {
-                        break;
-                    }
-                }
-                if chr == '\n' || chr == '\r' {
-                    // the cursor is sent back to the x index
-                    // instead of rolling back on the left of the screen
-                    pos = self.coord_to_index(std::cmp::max(0, x), y);
-                    continue;
-                }
-                // tabs are ignored, replaced by space
-                if chr == '\t' {
-                    chr = ' ';
-                }
+        self.print_fbg_attr(x, y, string, fg, bg, Attributes::default())
+    }
 
-                if ignore_count <= 0 {
-                    // write on the screen until the row changes,
-                    // skip the rest until a \n character is found
-                    if origin_row == pos / self.get_width() as usize {
-                        self.screen[pos] = pixel::pxl_fbg(chr, fg, bg);
-                        pos += 1;
-                    }
-                } else {
-                    ignore_count -= 1;
-                }
-            }
-        }
+    /// prints a string at the specified coordinates with the specified text attributes  
+    /// The string will be cropped if it reach the right border
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::Attribute;
+    ///
+    /// // print "Hello, world" in bold
+    /// screen.print_attr(0, 0, "Hello, world!", Attribute::Bold.into());
+    /// ```
+    pub fn print_attr(&mut self, x: i32, y: i32, string: &str, attr: Attributes) {
+        self.print_fbg_attr(x, y, string, Color::Reset, Color::Reset, attr)
+    }
+
+    /// prints a string at the specified coordinates with the specified foreground and background color and text attributes  
+    /// The string will be cropped if it reach the right border
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::{Attribute, Color};
+    ///
+    /// // print an underlined "Hello, world" in blue on white background
+    /// screen.print_fbg_attr(0, 0, "Hello, world!", Color::Blue, Color::White, Attribute::Underlined.into());
+    /// ```
+    pub fn print_fbg_attr(
+        &mut self,
+        x: i32,
+        y: i32,
+        string: &str,
+        fg: Color,
+        bg: Color,
+        attr: Attributes,
+    ) {
+        let (width, height) = (self.width, self.height);
+        crate::shapes::text(
+            x,
+            y,
+            string,
+            fg,
+            bg,
+            attr,
+            width,
+            height,
+            &mut |x, y, pixel| self.set_pxl(x, y, pixel),
+        );
     }
 
     /// Prints another screen on specified coordinates.
@@ -294,72 +347,17 @@ impl Screen {
     /// screen.line(0, 0, 9, 9, pixel::pxl('#'));
     /// ```
     pub fn line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, character: Pixel) {
-        let delta_x = end_x - start_x;
-        let delta_y = end_y - start_y;
-        // use optimized functions for pure horizontal or vertical lines
-        if delta_y == 0 {
+        if start_y == end_y {
             self.h_line(start_x, start_y, end_x, character);
             return;
         }
-        if delta_x == 0 {
+        if start_x == end_x {
             self.v_line(start_x, start_y, end_y, character);
             return;
         }
-
-        // Bresenham's line algorithm
-        let line_low = |screen: &mut Screen, x0: i32, y0: i32, x1: i32, y1: i32| {
-            let dx: i32 = x1 - x0;
-            let mut dy: i32 = y1 - y0;
-            let mut yi = 1;
-            if dy < 0 {
-                yi = -1;
-                dy = -dy;
-            }
-            let mut d = 2 * dy - dx;
-            let mut y = y0;
-
-            for x in x0..x1 + 1 {
-                screen.set_pxl(x, y, character);
-                if d > 0 {
-                    y += yi;
-                    d -= 2 * dx;
-                }
-                d += 2 * dy;
-            }
-        };
-
-        let line_high = |screen: &mut Screen, x0: i32, y0: i32, x1: i32, y1: i32| {
-            let mut dx = x1 - x0;
-            let dy = y1 - y0;
-            let mut xi = 1;
-            if dx < 0 {
-                xi = -1;
-                dx = -dx;
-            }
-            let mut d = 2 * dx - dy;
-            let mut x = x0;
-
-            for y in y0..y1 + 1 {
-                screen.set_pxl(x, y, character);
-                if d > 0 {
-                    x += xi;
-                    d -= 2 * dy;
-                }
-                d += 2 * dx;
-            }
-        };
-
-        if (end_y - start_y).abs() < (end_x - start_x).abs() {
-            if start_x > end_x {
-                line_low(self, end_x, end_y, start_x, start_y);
-            } else {
-                line_low(self, start_x, start_y, end_x, end_y);
-            }
-        } else if start_y > end_y {
-            line_high(self, end_x, end_y, start_x, start_y);
-        } else {
-            line_high(self, start_x, start_y, end_x, end_y);
-        }
+        crate::shapes::line(start_x, start_y, end_x, end_y, &mut |x, y| {
+            self.set_pxl(x, y, character)
+        });
     }
 
     /// Draws a rectangle of the provided character between two sets of coordinates  
@@ -432,6 +430,34 @@ impl Screen {
         }
     }
 
+    /// Fill a rectangle between two sets of coordinates with the colors of a gradient, as background of blank pixels
+    ///
+    /// usage:
+    /// ```
+    /// use console_engine::color::{Gradient, GradientDirection};
+    /// // ...
+    /// screen.fill_gradient(0, 0, 19, 0, &Gradient::health(), GradientDirection::Horizontal);
+    /// ```
+    pub fn fill_gradient(
+        &mut self,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        gradient: &Gradient,
+        direction: GradientDirection,
+    ) {
+        crate::shapes::gradient_rect(
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+            gradient,
+            direction,
+            &mut |x, y, color| self.set_pxl(x, y, pixel::pxl_bg(' ', color)),
+        );
+    }
+
     /// Draws a circle of the provided character at an x and y position with a radius
     /// see: [olcPixelGameEngine Repository](https://github.com/OneLoneCoder/olcPixelGameEngine)
     ///
@@ -442,31 +468,7 @@ impl Screen {
     /// screen.circle(10, 10, 4, pixel::pxl('#'));
     /// ```
     pub fn circle(&mut self, x: i32, y: i32, radius: u32, character: Pixel) {
-        let mut relative_pos_x = 0;
-        let mut relative_pos_y = radius as i32;
-        let mut distance: i32 = 3 - 2 * radius as i32;
-        if radius == 0 {
-            return;
-        }
-
-        while relative_pos_y >= relative_pos_x {
-            self.set_pxl(x + relative_pos_x, y - relative_pos_y, character);
-            self.set_pxl(x + relative_pos_y, y - relative_pos_x, character);
-            self.set_pxl(x + relative_pos_y, y + relative_pos_x, character);
-            self.set_pxl(x + relative_pos_x, y + relative_pos_y, character);
-            self.set_pxl(x - relative_pos_x, y + relative_pos_y, character);
-            self.set_pxl(x - relative_pos_y, y + relative_pos_x, character);
-            self.set_pxl(x - relative_pos_y, y - relative_pos_x, character);
-            self.set_pxl(x - relative_pos_x, y - relative_pos_y, character);
-            if distance < 0 {
-                distance += 4 * relative_pos_x + 6;
-                relative_pos_x += 1;
-            } else {
-                distance += 4 * (relative_pos_x - relative_pos_y) + 10;
-                relative_pos_x += 1;
-                relative_pos_y -= 1;
-            }
-        }
+        crate::shapes::circle(x, y, radius, &mut |x, y| self.set_pxl(x, y, character));
     }
 
     /// Fill a circle of the provided character at an x and y position with a radius
@@ -479,36 +481,7 @@ impl Screen {
     /// screen.fill_circle(10, 10, 4, pixel::pxl('#'));
     /// ```
     pub fn fill_circle(&mut self, x: i32, y: i32, radius: u32, character: Pixel) {
-        // Taken from wikipedia
-        let mut relative_pos_x = 0;
-        let mut relative_pos_y = radius as i32;
-        let mut distance: i32 = 3 - 2 * radius as i32;
-        if radius == 0 {
-            return;
-        }
-
-        // create a lambda function that draw fast horizontal lines
-        let mut drawline = |start_x: i32, end_x: i32, y: i32| {
-            for i in start_x..end_x + 1 {
-                self.set_pxl(i, y, character);
-            }
-        };
-
-        while relative_pos_y >= relative_pos_x {
-            // Modified to draw scan-lines instead of edges
-            drawline(x - relative_pos_x, x + relative_pos_x, y - relative_pos_y);
-            drawline(x - relative_pos_y, x + relative_pos_y, y - relative_pos_x);
-            drawline(x - relative_pos_x, x + relative_pos_x, y + relative_pos_y);
-            drawline(x - relative_pos_y, x + relative_pos_y, y + relative_pos_x);
-            if distance < 0 {
-                distance += 4 * relative_pos_x + 6;
-                relative_pos_x += 1;
-            } else {
-                distance += 4 * (relative_pos_x - relative_pos_y) + 10;
-                relative_pos_x += 1;
-                relative_pos_y -= 1;
-            }
-        }
+        crate::shapes::fill_circle(x, y, radius, &mut |x, y| self.set_pxl(x, y, character));
     }
 
     /// Draws a triangle of the provided character using three sets of coordinates
@@ -556,82 +529,10 @@ impl Screen {
         character: Pixel,
     ) {
         self.triangle(x1, y1, x2, y2, x3, y3, character);
-        // we use tuples for this for now
-        let v0 = (x1, y1);
-        let mut v1 = (x2, y2);
-        let mut v2 = (x3, y3);
-
-        // algorithm only fills counter clockwise triangles, so swap as needed
-        // For a triangle A B C, you can find the winding by computing the cross product (B - A) x (C - A). For 2d tri's, with z=0, it will only have a z component.
-        // To give all the same winding, swap vertices C and B if this z component is negative.
-        let cross = (v1.1 - v0.1) * (v2.0 - v1.0) - (v1.0 - v0.0) * (v2.1 - v1.1);
-        if cross > 0 {
-            std::mem::swap(&mut v1, &mut v2)
-        }
-
-        // Compute triangle bounding box and clip to screen bounds
-        let min_x = std::cmp::max(std::cmp::min(std::cmp::min(v0.0, v1.0), v2.0), 0);
-        let max_x = std::cmp::min(
-            std::cmp::max(std::cmp::max(v0.0, v1.0), v2.0),
-            self.get_width() as i32 - 1,
-        );
-        let min_y = std::cmp::max(std::cmp::min(std::cmp::min(v0.1, v1.1), v2.1), 0);
-        let max_y = std::cmp::min(
-            std::cmp::max(std::cmp::max(v0.1, v1.1), v2.1),
-            self.get_height() as i32 - 1,
-        );
-
-        // Triangle setup
-        let a01 = v0.1 - v1.1;
-        let b01 = v1.0 - v0.0;
-        let a12 = v1.1 - v2.1;
-        let b12 = v2.0 - v1.0;
-        let a20 = v2.1 - v0.1;
-        let b20 = v0.0 - v2.0;
-
-        // Determine edges
-        let is_top_left = |v0: (i32, i32), v1: (i32, i32)| -> bool { v0.1 > v1.1 };
-
-        // We follow fill rules and add a bias
-        let bias0 = if is_top_left(v1, v2) { 0 } else { -1 };
-        let bias1 = if is_top_left(v2, v0) { 0 } else { -1 };
-        let bias2 = if is_top_left(v0, v1) { 0 } else { -1 };
-
-        // Determine barycentric coordinates
-        let orient2d = |a: (i32, i32), b: (i32, i32), c: (i32, i32)| -> i32 {
-            (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
-        };
-
-        let mut p = (min_x, min_y);
-        let mut w0_row = orient2d(v1, v2, p) + bias0;
-        let mut w1_row = orient2d(v2, v0, p) + bias1;
-        let mut w2_row = orient2d(v0, v1, p) + bias2;
-
-        // Rasterize
-        for y in min_y..max_y {
-            p.1 = y;
-            // Barycentric coordinates at start of row
-            let mut w0 = w0_row;
-            let mut w1 = w1_row;
-            let mut w2 = w2_row;
-
-            for x in min_x..max_x {
-                p.0 = x;
-                // If p is on or inside all edges, render pixel.
-                if (w0 | w1 | w2) >= 0 {
-                    self.set_pxl(p.0, p.1, character);
-                }
-
-                // One step to the right
-                w0 += a12;
-                w1 += a20;
-                w2 += a01;
-            }
-            // One row step
-            w0_row += b12;
-            w1_row += b20;
-            w2_row += b01;
-        }
+        let (width, height) = (self.get_width(), self.get_height());
+        crate::shapes::fill_triangle(x1, y1, x2, y2, x3, y3, width, height, &mut |x, y| {
+            self.set_pxl(x, y, character)
+        });
     }
 
     /// Scrolls the screen for a certain amount of characters vertically or horizontally
@@ -657,6 +558,9 @@ impl Screen {
     pub fn scroll(&mut self, h_scroll: i32, v_scroll: i32, background: Pixel) {
         let width = self.width as i32;
         let height = self.height as i32;
+        if h_scroll != 0 || v_scroll != 0 {
+            self.mark_dirty();
+        }
         if h_scroll != 0 {
             // if the scroll is beyond the size of the screen, simply clear it
             if h_scroll >= width || h_scroll <= -width {
@@ -743,7 +647,10 @@ impl Screen {
     pub fn set_pxl(&mut self, x: i32, y: i32, character: Pixel) {
         if x >= 0 && y >= 0 && x < self.width as i32 && y < self.height as i32 {
             let index = self.coord_to_index(x, y);
-            self.screen[index] = character;
+            if self.screen[index] != character {
+                self.screen[index] = character;
+                self.dirty_rows[y as usize] = true;
+            }
         }
     }
 
@@ -755,17 +662,16 @@ impl Screen {
     ///     screen.print(0,0,"Found a 'o'");
     /// }
     /// ```
-    pub fn get_pxl(&self, x: i32, y: i32) -> Result<Pixel, String> {
+    pub fn get_pxl(&self, x: i32, y: i32) -> Result<Pixel, Error> {
         if x >= 0 && y >= 0 && x < self.width as i32 && y < self.height as i32 {
             return Ok(self.screen[self.coord_to_index(x, y)]);
         }
-        Err(format!(
-            "Attempted to get_pxl out of bounds (coords: [{}, {}], bounds: [{}, {}])",
+        Err(Error::OutOfBounds {
             x,
             y,
-            self.width - 1,
-            self.height - 1
-        ))
+            width: self.width,
+            height: self.height,
+        })
     }
 
     /// Resizes the screen to match the given width and height
@@ -790,6 +696,7 @@ impl Screen {
         self.screen = new_screen;
         self.width = new_width;
         self.height = new_height;
+        self.dirty_rows = vec![true; new_height as usize];
     }
 
     /// Extracts part of the current screen as a separate Screen object
@@ -857,9 +764,23 @@ impl Screen {
     ///
     /// You should not use this function while a ConsoleEngine is running.
     /// You may want to use ConsoleEngine's `print_screen`, `print_screen_alpha` or `set_screen` instead
+    ///
+    /// Panics if the terminal can't be written to, see [try_draw](#method.try_draw)
     pub fn draw(&self) {
-        let mut output = std::io::stdout();
-        crossterm::terminal::enable_raw_mode().unwrap();
+        self.try_draw().unwrap();
+    }
+
+    /// Prints the screen in the terminal like [draw](#method.draw),
+    /// returning an error instead of panicking if the terminal can't be written to
+    pub fn try_draw(&self) -> Result<(), Error> {
+        crossterm::terminal::enable_raw_mode()?;
+        let result = self.write_to(&mut std::io::stdout());
+        // restore the terminal even if the screen couldn't be written
+        crossterm::terminal::disable_raw_mode()?;
+        result
+    }
+
+    fn write_to<W: std::io::Write>(&self, output: &mut W) -> Result<(), Error> {
         let mut skip_next = false;
         for i in 0..self.width * self.height {
             let pixel = &self.screen[i as usize];
@@ -867,23 +788,151 @@ impl Screen {
                 skip_next = false;
                 continue;
             }
-            if let Some(char_width) = unicode_width::UnicodeWidthChar::width(pixel.chr) {
-                if char_width > 1 {
-                    skip_next = true;
-                }
+            if pixel.width() > 1 {
+                skip_next = true;
             }
             execute!(
                 output,
+                style::SetAttribute(Attribute::Reset),
+                style::SetAttributes(pixel.attr),
                 style::SetForegroundColor(pixel.fg),
                 style::SetBackgroundColor(pixel.bg),
-                style::Print(pixel.chr)
-            )
-            .unwrap();
+                style::Print(pixel)
+            )?;
             if i != self.width * self.height - 1 && i % self.width == self.width - 1 {
-                execute!(output, style::Print("\r\n")).unwrap();
+                execute!(output, style::Print("\r\n"))?;
             }
         }
-        crossterm::terminal::disable_raw_mode().unwrap();
+        execute!(output, style::SetAttribute(Attribute::Reset))?;
+        Ok(())
+    }
+
+    /// Renders the characters of the screen as plain text, one line per row
+    ///
+    /// The cell following a wide character is skipped, like when drawing into the terminal,
+    /// and empty (`'\u{0}'`) characters are rendered as spaces.
+    ///
+    /// usage:
+    /// ```
+    /// let mut screen = Screen::new(5, 2);
+    /// screen.print(0, 0, "Hello");
+    /// assert_eq!(screen.to_text(), "Hello\n     ");
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity(((self.width + 1) * self.height) as usize);
+        for y in 0..self.height as i32 {
+            if y > 0 {
+                text.push('\n');
+            }
+            let mut skip_next = false;
+            for x in 0..self.width as i32 {
+                if skip_next {
+                    skip_next = false;
+                    continue;
+                }
+                let pixel = self.screen[self.coord_to_index(x, y)];
+                if pixel.width() > 1 {
+                    skip_next = true;
+                }
+                if pixel.chr == '\u{0}' {
+                    text.push(' ');
+                } else {
+                    text.push(pixel.chr);
+                    text.push_str(pixel.combining.as_str());
+                }
+            }
+        }
+        text
+    }
+
+    /// Renders the screen as text annotated with its colors
+    ///
+    /// The output contains the [plain text](#method.to_text) of the screen, followed by a foreground
+    /// and a background map where each color is represented by a symbol (`.` being `Color::Reset`),
+    /// an attribute map where each set of text attributes is represented by a symbol (`.` being no attribute),
+    /// and legends giving the color and attributes behind each symbol.
+    /// Symbols are given in order of appearance, so the output is stable and can be stored as a snapshot.
+    /// A legend with more than 63 entries uses symbols of several characters, so every entry keeps its own symbol.
+    ///
+    /// usage:
+    /// ```
+    /// let mut screen = Screen::new(3, 1);
+    /// screen.print_fbg(0, 0, "Hi!", Color::Blue, Color::Reset);
+    /// println!("{}", screen.to_annotated());
+    /// // text:
+    /// // Hi!
+    /// // fg:
+    /// // aaa
+    /// // bg:
+    /// // ...
+    /// // attr:
+    /// // ...
+    /// // legend:
+    /// // a: Blue
+    /// // attributes:
+    /// ```
+    pub fn to_annotated(&self) -> String {
+        // index of each value in its legend, in order of appearance
+        fn index_of<T: PartialEq>(legend: &mut Vec<T>, value: T) -> usize {
+            legend.iter().position(|v| *v == value).unwrap_or_else(|| {
+                legend.push(value);
+                legend.len() - 1
+            })
+        }
+        let mut legend: Vec<Color> = vec![Color::Reset];
+        let mut attr_legend: Vec<Attributes> = vec![Attributes::default()];
+        let fg: Vec<usize> = self
+            .screen
+            .iter()
+            .map(|p| index_of(&mut legend, p.fg))
+            .collect();
+        let bg: Vec<usize> = self
+            .screen
+            .iter()
+            .map(|p| index_of(&mut legend, p.bg))
+            .collect();
+        let attr: Vec<usize> = self
+            .screen
+            .iter()
+            .map(|p| index_of(&mut attr_legend, p.attr))
+            .collect();
+        // symbols get longer once a legend is too large for single characters, so every entry stays distinct
+        let symbol_width = legend_symbol_width(legend.len());
+        let attr_symbol_width = legend_symbol_width(attr_legend.len());
+        let map = |indexes: &[usize], width: usize| -> String {
+            let mut map = String::with_capacity(((self.width + 1) * self.height) as usize * width);
+            for (i, index) in indexes.iter().enumerate() {
+                if i > 0 && i % self.width as usize == 0 {
+                    map.push('\n');
+                }
+                map.push_str(&legend_symbol(*index, width));
+            }
+            map
+        };
+
+        let mut annotated = format!(
+            "text:\n{}\nfg:\n{}\nbg:\n{}\nattr:\n{}\nlegend:\n",
+            self.to_text(),
+            map(&fg, symbol_width),
+            map(&bg, symbol_width),
+            map(&attr, attr_symbol_width)
+        );
+        for (index, color) in legend.iter().enumerate().skip(1) {
+            annotated.push_str(&format!(
+                "{}: {:?}\n",
+                legend_symbol(index, symbol_width),
+                color
+            ));
+        }
+        annotated.push_str("attributes:\n");
+        for (index, attr) in attr_legend.iter().enumerate().skip(1) {
+            annotated.push_str(&format!(
+                "{}: {:?}\n",
+                legend_symbol(index, attr_symbol_width),
+                attribute_list(*attr)
+            ));
+        }
+        annotated
     }
 
     /// Converts x and y coordinates to screen index
@@ -894,3 +943,198 @@ impl Screen {
         ((y * self.width as i32) + x) as usize
     }
 }
+
+/// Characters of the symbols used by [to_annotated](struct.Screen.html#method.to_annotated)
+const LEGEND_SYMBOLS: &[u8] = b".abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Number of characters needed by the symbols of a legend with the given number of entries
+fn legend_symbol_width(entries: usize) -> usize {
+    let mut width = 1;
+    let mut capacity = LEGEND_SYMBOLS.len();
+    while capacity < entries {
+        width += 1;
+        capacity = capacity.saturating_mul(LEGEND_SYMBOLS.len());
+    }
+    width
+}
+
+/// Symbol used by [to_annotated](struct.Screen.html#method.to_annotated) for the nth entry of a legend,
+/// written with the given number of characters
+fn legend_symbol(mut index: usize, width: usize) -> String {
+    let mut symbol = vec![LEGEND_SYMBOLS[0]; width];
+    for chr in symbol.iter_mut().rev() {
+        *chr = LEGEND_SYMBOLS[index % LEGEND_SYMBOLS.len()];
+        index /= LEGEND_SYMBOLS.len();
+    }
+    String::from_utf8(symbol).unwrap()
+}
+
+/// Lists the attributes contained in an attribute set
+pub(crate) fn attribute_list(attr: Attributes) -> Vec<Attribute> {
+    Attribute::iterator().filter(|a| attr.has(*a)).collect()
+}
+
+impl PartialEq for Screen {
+    /// Two screens are equal if they have the same size and the same pixels
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.screen == other.screen
+    }
+}
+
+impl Eq for Screen {}
+
+impl fmt::Debug for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Screen")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("rows", &self.to_text().lines().collect::<Vec<&str>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::Color;
+
+    use super::Screen;
+    use crate::pixel;
+
+    #[test]
+    fn print_graphemes() {
+        let mut screen = Screen::new(8, 1);
+        // combining accent, emoji with skin tone modifier, flag
+        screen.print(0, 0, "e\u{301}👍🏽🇫🇷!");
+        assert_eq!(screen.get_pxl(0, 0).unwrap().grapheme(), "e\u{301}");
+        assert_eq!(screen.get_pxl(1, 0).unwrap().grapheme(), "👍🏽");
+        assert_eq!(screen.get_pxl(2, 0).unwrap().chr, ' ');
+        assert_eq!(screen.get_pxl(3, 0).unwrap().grapheme(), "🇫🇷");
+        assert_eq!(screen.get_pxl(4, 0).unwrap().chr, ' ');
+        assert_eq!(screen.get_pxl(5, 0).unwrap().chr, '!');
+        assert_eq!(screen.to_text(), "e\u{301}👍🏽🇫🇷!  ");
+    }
+
+    #[test]
+    fn print_wide_borders() {
+        let mut screen = Screen::new(3, 2);
+        // the continuation cell keeps the colors of the grapheme
+        screen.print_fbg(0, 0, "a👍", Color::Red, Color::Blue);
+        assert_eq!(
+            screen.get_pxl(2, 0).unwrap(),
+            pixel::pxl_fbg(' ', Color::Red, Color::Blue)
+        );
+
+        // wide graphemes cut by the right border are replaced by a space
+        screen.print(0, 1, "ab👍");
+        assert_eq!(screen.to_text(), "a👍\nab ");
+
+        // wide graphemes cut by the left border only show their continuation cell
+        let mut screen = Screen::new_fill(3, 1, pixel::pxl('#'));
+        screen.print(-1, 0, "👍ab");
+        assert_eq!(screen.to_text(), " ab");
+    }
+
+    #[test]
+    fn print_new_lines() {
+        let mut screen = Screen::new(3, 3);
+        screen.print(1, 0, "ab\r\ncd\n\tef");
+        assert_eq!(screen.to_text(), " ab\n cd\n  e");
+    }
+
+    #[test]
+    fn annotated_many_colors() {
+        let mut screen = Screen::new(70, 1);
+        for x in 0..70 {
+            screen.set_pxl(x, 0, pixel::pxl_fg('#', Color::AnsiValue(x as u8)));
+        }
+        let annotated = screen.to_annotated();
+        let fg = annotated.lines().nth(3).unwrap();
+        // 71 colors with Reset: two characters per symbol
+        assert_eq!(fg.len(), 140);
+        assert!(fg.starts_with(".a.b"));
+        assert!(fg.ends_with("aeafag"));
+        assert!(annotated.contains("\nag: AnsiValue(69)\n"));
+        // the background only uses Reset, but shares the legend of the foreground
+        assert_eq!(annotated.lines().nth(5).unwrap(), "..".repeat(70));
+    }
+
+    #[test]
+    fn dirty_rows() {
+        let mut screen = Screen::new(3, 3);
+        assert!(screen.is_row_dirty(0) && screen.is_row_dirty(2));
+        screen.clear_dirty();
+        // drawing the same pixel again doesn't modify the row
+        screen.set_pxl(0, 0, pixel::pxl(' '));
+        screen.print(0, 1, "ab");
+        assert!(!screen.is_row_dirty(0));
+        assert!(screen.is_row_dirty(1));
+        assert!(!screen.is_row_dirty(2));
+
+        // clearing only modifies the rows that weren't blank
+        screen.clear_dirty();
+        screen.clear();
+        assert!(!screen.is_row_dirty(0));
+        assert!(screen.is_row_dirty(1));
+
+        let mut last_frame = Screen::new(3, 3);
+        last_frame.copy_dirty_rows(&screen);
+        screen.print(0, 2, "c");
+        last_frame.copy_dirty_rows(&screen);
+        assert_eq!(last_frame, screen);
+        assert!(!screen.is_row_dirty(3));
+    }
+
+    #[test]
+    fn fill_gradient() {
+        use crate::color::{Gradient, GradientDirection};
+
+        let black = Color::Rgb { r: 0, g: 0, b: 0 };
+        let white = Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        };
+        let bg = |screen: &Screen, x, y| screen.get_pxl(x, y).unwrap().bg;
+        let mut screen = Screen::new(5, 5);
+        // the gradient goes from the start to the end coordinates
+        screen.fill_gradient(
+            4,
+            0,
+            0,
+            0,
+            &Gradient::grayscale(),
+            GradientDirection::Horizontal,
+        );
+        assert_eq!((bg(&screen, 4, 0), bg(&screen, 0, 0)), (black, white));
+        assert_eq!(
+            bg(&screen, 2, 0),
+            Color::Rgb {
+                r: 128,
+                g: 128,
+                b: 128
+            }
+        );
+        assert_eq!(bg(&screen, 2, 1), Color::Reset);
+
+        screen.fill_gradient(
+            0,
+            1,
+            0,
+            4,
+            &Gradient::grayscale(),
+            GradientDirection::Vertical,
+        );
+        assert_eq!((bg(&screen, 0, 1), bg(&screen, 0, 4)), (black, white));
+
+        screen.fill_gradient(
+            0,
+            0,
+            4,
+            4,
+            &Gradient::grayscale(),
+            GradientDirection::Radial,
+        );
+        assert_eq!(bg(&screen, 2, 2), black);
+        assert_eq!((bg(&screen, 2, 0), bg(&screen, 0, 0)), (white, white));
+    }
+}
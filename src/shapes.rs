@@ -0,0 +1,357 @@
+//! Rasterization algorithms shared by every drawing surface
+//!
+//! Each function computes the points of a shape and hands them to a `plot` callback,
+//! which is in charge of actually drawing them (and ignoring out of bounds points).
+
+use crossterm::style::{Attributes, Color};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::color::{Gradient, GradientDirection};
+use crate::pixel::{self, Pixel};
+
+/// Plots a line between two sets of coordinates
+/// see: [Bresenham's line algorithm](https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm)
+pub(crate) fn line<F: FnMut(i32, i32)>(
+    start_x: i32,
+    start_y: i32,
+    end_x: i32,
+    end_y: i32,
+    plot: &mut F,
+) {
+    // pure horizontal or vertical lines
+    if start_y == end_y {
+        for x in start_x.min(end_x)..=start_x.max(end_x) {
+            plot(x, start_y);
+        }
+        return;
+    }
+    if start_x == end_x {
+        for y in start_y.min(end_y)..=start_y.max(end_y) {
+            plot(start_x, y);
+        }
+        return;
+    }
+
+    let line_low = |plot: &mut F, x0: i32, y0: i32, x1: i32, y1: i32| {
+        let dx: i32 = x1 - x0;
+        let mut dy: i32 = y1 - y0;
+        let mut yi = 1;
+        if dy < 0 {
+            yi = -1;
+            dy = -dy;
+        }
+        let mut d = 2 * dy - dx;
+        let mut y = y0;
+
+        for x in x0..x1 + 1 {
+            plot(x, y);
+            if d > 0 {
+                y += yi;
+                d -= 2 * dx;
+            }
+            d += 2 * dy;
+        }
+    };
+
+    let line_high = |plot: &mut F, x0: i32, y0: i32, x1: i32, y1: i32| {
+        let mut dx = x1 - x0;
+        let dy = y1 - y0;
+        let mut xi = 1;
+        if dx < 0 {
+            xi = -1;
+            dx = -dx;
+        }
+        let mut d = 2 * dx - dy;
+        let mut x = x0;
+
+        for y in y0..y1 + 1 {
+            plot(x, y);
+            if d > 0 {
+                x += xi;
+                d -= 2 * dy;
+            }
+            d += 2 * dx;
+        }
+    };
+
+    if (end_y - start_y).abs() < (end_x - start_x).abs() {
+        if start_x > end_x {
+            line_low(plot, end_x, end_y, start_x, start_y);
+        } else {
+            line_low(plot, start_x, start_y, end_x, end_y);
+        }
+    } else if start_y > end_y {
+        line_high(plot, end_x, end_y, start_x, start_y);
+    } else {
+        line_high(plot, start_x, start_y, end_x, end_y);
+    }
+}
+
+/// Plots the outline of a circle
+/// see: [olcPixelGameEngine Repository](https://github.com/OneLoneCoder/olcPixelGameEngine)
+pub(crate) fn circle<F: FnMut(i32, i32)>(x: i32, y: i32, radius: u32, plot: &mut F) {
+    let mut relative_pos_x = 0;
+    let mut relative_pos_y = radius as i32;
+    let mut distance: i32 = 3 - 2 * radius as i32;
+    if radius == 0 {
+        return;
+    }
+
+    while relative_pos_y >= relative_pos_x {
+        plot(x + relative_pos_x, y - relative_pos_y);
+        plot(x + relative_pos_y, y - relative_pos_x);
+        plot(x + relative_pos_y, y + relative_pos_x);
+        plot(x + relative_pos_x, y + relative_pos_y);
+        plot(x - relative_pos_x, y + relative_pos_y);
+        plot(x - relative_pos_y, y + relative_pos_x);
+        plot(x - relative_pos_y, y - relative_pos_x);
+        plot(x - relative_pos_x, y - relative_pos_y);
+        if distance < 0 {
+            distance += 4 * relative_pos_x + 6;
+            relative_pos_x += 1;
+        } else {
+            distance += 4 * (relative_pos_x - relative_pos_y) + 10;
+            relative_pos_x += 1;
+            relative_pos_y -= 1;
+        }
+    }
+}
+
+/// Plots a filled circle
+/// see: [olcPixelGameEngine Repository](https://github.com/OneLoneCoder/olcPixelGameEngine)
+pub(crate) fn fill_circle<F: FnMut(i32, i32)>(x: i32, y: i32, radius: u32, plot: &mut F) {
+    // Taken from wikipedia
+    let mut relative_pos_x = 0;
+    let mut relative_pos_y = radius as i32;
+    let mut distance: i32 = 3 - 2 * radius as i32;
+    if radius == 0 {
+        return;
+    }
+
+    // create a lambda function that draw fast horizontal lines
+    let mut drawline = |start_x: i32, end_x: i32, y: i32| {
+        for i in start_x..end_x + 1 {
+            plot(i, y);
+        }
+    };
+
+    while relative_pos_y >= relative_pos_x {
+        // Modified to draw scan-lines instead of edges
+        drawline(x - relative_pos_x, x + relative_pos_x, y - relative_pos_y);
+        drawline(x - relative_pos_y, x + relative_pos_y, y - relative_pos_x);
+        drawline(x - relative_pos_x, x + relative_pos_x, y + relative_pos_y);
+        drawline(x - relative_pos_y, x + relative_pos_y, y + relative_pos_x);
+        if distance < 0 {
+            distance += 4 * relative_pos_x + 6;
+            relative_pos_x += 1;
+        } else {
+            distance += 4 * (relative_pos_x - relative_pos_y) + 10;
+            relative_pos_x += 1;
+            relative_pos_y -= 1;
+        }
+    }
+}
+
+/// Plots a filled triangle, clipped to a surface of the given width and height
+/// see: [rustyPixelGameEngine Repository](https://github.com/mattbettcher/rustyPixelGameEngine)
+///
+/// The outline of the triangle is not plotted, see [line]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn fill_triangle<F: FnMut(i32, i32)>(
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+    x3: i32,
+    y3: i32,
+    width: u32,
+    height: u32,
+    plot: &mut F,
+) {
+    // we use tuples for this for now
+    let v0 = (x1, y1);
+    let mut v1 = (x2, y2);
+    let mut v2 = (x3, y3);
+
+    // algorithm only fills counter clockwise triangles, so swap as needed
+    // For a triangle A B C, you can find the winding by computing the cross product (B - A) x (C - A). For 2d tri's, with z=0, it will only have a z component.
+    // To give all the same winding, swap vertices C and B if this z component is negative.
+    let cross = (v1.1 - v0.1) * (v2.0 - v1.0) - (v1.0 - v0.0) * (v2.1 - v1.1);
+    if cross > 0 {
+        std::mem::swap(&mut v1, &mut v2)
+    }
+
+    // Compute triangle bounding box and clip to screen bounds
+    let min_x = std::cmp::max(std::cmp::min(std::cmp::min(v0.0, v1.0), v2.0), 0);
+    let max_x = std::cmp::min(
+        std::cmp::max(std::cmp::max(v0.0, v1.0), v2.0),
+        width as i32 - 1,
+    );
+    let min_y = std::cmp::max(std::cmp::min(std::cmp::min(v0.1, v1.1), v2.1), 0);
+    let max_y = std::cmp::min(
+        std::cmp::max(std::cmp::max(v0.1, v1.1), v2.1),
+        height as i32 - 1,
+    );
+
+    // Triangle setup
+    let a01 = v0.1 - v1.1;
+    let b01 = v1.0 - v0.0;
+    let a12 = v1.1 - v2.1;
+    let b12 = v2.0 - v1.0;
+    let a20 = v2.1 - v0.1;
+    let b20 = v0.0 - v2.0;
+
+    // Determine edges
+    let is_top_left = |v0: (i32, i32), v1: (i32, i32)| -> bool { v0.1 > v1.1 };
+
+    // We follow fill rules and add a bias
+    let bias0 = if is_top_left(v1, v2) { 0 } else { -1 };
+    let bias1 = if is_top_left(v2, v0) { 0 } else { -1 };
+    let bias2 = if is_top_left(v0, v1) { 0 } else { -1 };
+
+    // Determine barycentric coordinates
+    let orient2d = |a: (i32, i32), b: (i32, i32), c: (i32, i32)| -> i32 {
+        (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+    };
+
+    let mut p = (min_x, min_y);
+    let mut w0_row = orient2d(v1, v2, p) + bias0;
+    let mut w1_row = orient2d(v2, v0, p) + bias1;
+    let mut w2_row = orient2d(v0, v1, p) + bias2;
+
+    // Rasterize
+    for y in min_y..max_y {
+        p.1 = y;
+        // Barycentric coordinates at start of row
+        let mut w0 = w0_row;
+        let mut w1 = w1_row;
+        let mut w2 = w2_row;
+
+        for x in min_x..max_x {
+            p.0 = x;
+            // If p is on or inside all edges, render pixel.
+            if (w0 | w1 | w2) >= 0 {
+                plot(p.0, p.1);
+            }
+
+            // One step to the right
+            w0 += a12;
+            w1 += a20;
+            w2 += a01;
+        }
+        // One row step
+        w0_row += b12;
+        w1_row += b20;
+        w2_row += b01;
+    }
+}
+
+/// Plots every cell of a rectangle with its color along the gradient.
+/// Horizontal and vertical gradients go from the start to the end coordinates,
+/// radial gradients go from the center to the borders of the rectangle
+pub(crate) fn gradient_rect<F: FnMut(i32, i32, Color)>(
+    start_x: i32,
+    start_y: i32,
+    end_x: i32,
+    end_y: i32,
+    gradient: &Gradient,
+    direction: GradientDirection,
+    plot: &mut F,
+) {
+    // position of a coordinate between the start and the end, from 0 to 1
+    let progress = |value: i32, start: i32, end: i32| {
+        if start == end {
+            0.0
+        } else {
+            (value - start) as f32 / (end - start) as f32
+        }
+    };
+    let center_x = (start_x + end_x) as f32 / 2.0;
+    let center_y = (start_y + end_y) as f32 / 2.0;
+    let radius_x = ((end_x - start_x).abs() as f32 / 2.0).max(0.5);
+    let radius_y = ((end_y - start_y).abs() as f32 / 2.0).max(0.5);
+    for y in start_y.min(end_y)..=start_y.max(end_y) {
+        for x in start_x.min(end_x)..=start_x.max(end_x) {
+            let position = match direction {
+                GradientDirection::Horizontal => progress(x, start_x, end_x),
+                GradientDirection::Vertical => progress(y, start_y, end_y),
+                // an ellipse fitting the rectangle, so the gradient isn't stretched by the shape of the cells
+                GradientDirection::Radial => {
+                    let dx = (x as f32 - center_x) / radius_x;
+                    let dy = (y as f32 - center_y) / radius_y;
+                    (dx * dx + dy * dy).sqrt()
+                }
+            };
+            plot(x, y, gradient.sample(position));
+        }
+    }
+}
+
+/// Plots the cells of a string printed at the given coordinates, clipped to a surface of the given width and height
+///
+/// Each grapheme is placed one by one, advancing by its display width:
+/// wide graphemes also plot a continuation cell (a blank pixel with the same colors),
+/// and are replaced by a blank pixel if they are cut by a border.
+/// `\n` (or `\r\n`) moves to the next row, `\r` goes back to the starting column and tabs are printed as spaces.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn text<F: FnMut(i32, i32, Pixel)>(
+    x: i32,
+    y: i32,
+    string: &str,
+    fg: Color,
+    bg: Color,
+    attr: Attributes,
+    width: u32,
+    height: u32,
+    plot: &mut F,
+) {
+    let (width, height) = (width as i32, height as i32);
+    let blank = pixel::pxl_fbg_attr(' ', fg, bg, attr);
+    let mut row = y;
+    let mut column = x;
+    for grapheme in string.graphemes(true) {
+        // "\r\n" is a single grapheme, process it as a new line
+        if grapheme == "\n" || grapheme == "\r\n" {
+            row += 1;
+            column = x;
+            if row >= height {
+                break;
+            }
+            continue;
+        }
+        if grapheme == "\r" {
+            // the cursor is sent back to the x index
+            // instead of rolling back on the left of the screen
+            column = x;
+            continue;
+        }
+        // rows above the surface and graphemes past its right border are skipped
+        if row < 0 || column >= width {
+            continue;
+        }
+        let pixel = if grapheme == "\t" {
+            // tabs are replaced by spaces
+            blank
+        } else {
+            // unwrap here because graphemes are never empty
+            let mut pixel = pixel::pxl_fbg_attr(grapheme.chars().next().unwrap(), fg, bg, attr);
+            pixel.set_grapheme(grapheme);
+            pixel
+        };
+        // wide graphemes also reserve the cell after them
+        let cells = pixel.width().clamp(1, 2) as i32;
+
+        if column + cells <= 0 {
+            // the grapheme is hidden on the left
+        } else if column < 0 || (cells > 1 && column + 1 >= width) {
+            // the grapheme is cut by a border, only its visible cell is drawn as a blank
+            plot(column.max(0), row, blank);
+        } else {
+            plot(column, row, pixel);
+            for i in 1..cells {
+                plot(column + i, row, blank);
+            }
+        }
+        column += cells;
+    }
+}
@@ -0,0 +1,263 @@
+//! Snapshot testing helpers for Screens
+//!
+//! These functions allow asserting on rendered output without walking `get_pxl` by hand:
+//! screens can be compared to each other with a readable diff, or against snapshot files stored on disk.
+//!
+//! Snapshots are stored in their [annotated form](../screen/struct.Screen.html#method.to_annotated),
+//! so both characters and colors are checked.
+//! Set the `CONSOLE_ENGINE_UPDATE_SNAPSHOTS` environment variable to `1` to create or update the stored snapshots
+//! instead of checking them.
+//!
+//! usage (within a test):
+//! ```no_run
+//! use console_engine::screen::Screen;
+//! use console_engine::snapshot;
+//!
+//! let mut screen = Screen::new(13, 1);
+//! screen.print(0, 0, "Hello, world!");
+//! // compared with tests/snapshots/hello.snap
+//! snapshot::assert_snapshot("hello", &screen);
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::screen::{attribute_list, Screen};
+
+/// Environment variable that makes snapshot assertions update the stored snapshots
+pub const UPDATE_ENV: &str = "CONSOLE_ENGINE_UPDATE_SNAPSHOTS";
+
+/// Lists every cell that differs between two screens
+///
+/// Returns `None` if both screens are equal
+///
+/// usage:
+/// ```
+/// if let Some(diff) = snapshot::diff(&expected, &actual) {
+///     println!("{}", diff);
+/// }
+/// ```
+pub fn diff(expected: &Screen, actual: &Screen) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    if expected.get_width() != actual.get_width() || expected.get_height() != actual.get_height() {
+        return Some(format!(
+            "screen sizes differ: expected {}x{}, got {}x{}\n{}",
+            expected.get_width(),
+            expected.get_height(),
+            actual.get_width(),
+            actual.get_height(),
+            diff_text(&expected.to_text(), &actual.to_text()).unwrap_or_default()
+        ));
+    }
+    let mut output = diff_text(&expected.to_text(), &actual.to_text()).unwrap_or_default();
+    for y in 0..expected.get_height() as i32 {
+        for x in 0..expected.get_width() as i32 {
+            // unwrap here because both screens have the same size
+            let left = expected.get_pxl(x, y).unwrap();
+            let right = actual.get_pxl(x, y).unwrap();
+            if left != right {
+                output.push_str(&format!(
+                    "[{}, {}]: expected {:?} (fg: {:?}, bg: {:?}, attr: {:?}), got {:?} (fg: {:?}, bg: {:?}, attr: {:?})\n",
+                    x,
+                    y,
+                    left.chr,
+                    left.fg,
+                    left.bg,
+                    attribute_list(left.attr),
+                    right.chr,
+                    right.fg,
+                    right.bg,
+                    attribute_list(right.attr)
+                ));
+            }
+        }
+    }
+    Some(output)
+}
+
+/// Compares two texts line by line
+///
+/// Returns `None` if both texts are equal, or a listing of the lines that differ,
+/// with markers under the columns that changed
+pub fn diff_text(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let mut output = String::new();
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        let left = expected_lines.get(i).copied();
+        let right = actual_lines.get(i).copied();
+        if left == right {
+            continue;
+        }
+        output.push_str(&format!("line {}:\n", i + 1));
+        output.push_str(&format!("- {}\n", left.unwrap_or("<missing>")));
+        output.push_str(&format!("+ {}\n", right.unwrap_or("<missing>")));
+        if let (Some(left), Some(right)) = (left, right) {
+            let mut left_chars = left.chars();
+            let mut right_chars = right.chars();
+            let mut markers = String::new();
+            loop {
+                match (left_chars.next(), right_chars.next()) {
+                    (None, None) => break,
+                    (l, r) if l == r => markers.push(' '),
+                    _ => markers.push('^'),
+                }
+            }
+            output.push_str(&format!("  {}\n", markers.trim_end()));
+        }
+    }
+    Some(output)
+}
+
+/// Asserts that two screens are equal, panicking with a readable diff otherwise
+pub fn assert_screen_eq(expected: &Screen, actual: &Screen) {
+    if let Some(diff) = diff(expected, actual) {
+        panic!("screens are not equal:\n{}", diff);
+    }
+}
+
+/// Asserts that a screen matches the snapshot of the given name
+///
+/// Snapshots are stored as `tests/snapshots/<name>.snap` in the crate being tested
+/// (using the `CARGO_MANIFEST_DIR` environment variable, or the current directory if unavailable).
+///
+/// If `CONSOLE_ENGINE_UPDATE_SNAPSHOTS` is set to `1`, the snapshot is written instead of checked.
+/// A missing snapshot fails the assertion, so a mistyped name or a forgotten file can't pass silently.
+pub fn assert_snapshot(name: &str, screen: &Screen) {
+    let dir = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    assert_snapshot_file(
+        dir.join("tests")
+            .join("snapshots")
+            .join(format!("{}.snap", name)),
+        screen,
+    );
+}
+
+/// Asserts that a screen matches the snapshot stored at the given path
+///
+/// See [assert_snapshot](fn.assert_snapshot.html)
+pub fn assert_snapshot_file<P: AsRef<Path>>(path: P, screen: &Screen) {
+    let path = path.as_ref();
+    let actual = screen.to_annotated();
+    let update = std::env::var(UPDATE_ENV).map(|v| v == "1").unwrap_or(false);
+    if update {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap_or_else(|e| {
+                panic!("could not create snapshot directory {:?}: {}", parent, e)
+            });
+        }
+        std::fs::write(path, actual)
+            .unwrap_or_else(|e| panic!("could not write snapshot {:?}: {}", path, e));
+        return;
+    }
+    if !path.exists() {
+        panic!(
+            "snapshot {:?} does not exist\nrun with {}=1 to create it",
+            path, UPDATE_ENV
+        );
+    }
+    let expected = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("could not read snapshot {:?}: {}", path, e));
+    if let Some(diff) = diff_text(&expected, &actual) {
+        panic!(
+            "screen does not match snapshot {:?}:\n{}\nrun with {}=1 to update it",
+            path, diff, UPDATE_ENV
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::{Attribute, Attributes, Color};
+
+    use crate::{pixel, screen::Screen};
+
+    #[test]
+    fn text() {
+        let mut screen = Screen::new(6, 2);
+        screen.print(0, 0, "Hello");
+        screen.set_pxl(0, 1, pixel::pxl('👍'));
+        screen.set_pxl(2, 1, pixel::pxl('!'));
+        assert_eq!(screen.to_text(), "Hello \n👍!   ");
+
+        let empty = Screen::new_empty(2, 1);
+        assert_eq!(empty.to_text(), "  ");
+    }
+
+    #[test]
+    fn annotated() {
+        let mut screen = Screen::new(4, 2);
+        screen.print_fbg(0, 0, "ab", Color::Blue, Color::Reset);
+        screen.print_fbg(1, 1, "cd", Color::Red, Color::Blue);
+        assert_eq!(
+            screen.to_annotated(),
+            "text:\nab  \n cd \nfg:\naa..\n.bb.\nbg:\n....\n.aa.\nattr:\n....\n....\nlegend:\na: Blue\nb: Red\nattributes:\n"
+        );
+
+        screen.print_attr(2, 0, "ef", Attribute::Bold.into());
+        screen.set_pxl(
+            3,
+            1,
+            pixel::pxl_attr('g', Attributes::from(Attribute::Bold) | Attribute::Italic),
+        );
+        assert!(screen
+            .to_annotated()
+            .ends_with("attr:\n..aa\n...b\nlegend:\na: Blue\nb: Red\nattributes:\na: [Bold]\nb: [Bold, Italic]\n"));
+    }
+
+    #[test]
+    fn diff() {
+        let mut expected = Screen::new(4, 2);
+        expected.print(0, 0, "abcd");
+        let mut actual = expected.clone();
+        assert!(super::diff(&expected, &actual).is_none());
+        super::assert_screen_eq(&expected, &actual);
+
+        actual.set_pxl(2, 0, pixel::pxl_fg('x', Color::Red));
+        let diff = super::diff(&expected, &actual).unwrap();
+        assert!(diff.contains("- abcd\n+ abxd\n    ^\n"));
+        assert!(diff.contains(
+            "[2, 0]: expected 'c' (fg: Reset, bg: Reset, attr: []), got 'x' (fg: Red, bg: Reset, attr: [])"
+        ));
+
+        let smaller = Screen::new(3, 2);
+        assert!(super::diff(&expected, &smaller)
+            .unwrap()
+            .starts_with("screen sizes differ: expected 4x2, got 3x2"));
+    }
+
+    #[test]
+    #[should_panic(expected = "screens are not equal")]
+    fn assert_screen_eq() {
+        super::assert_screen_eq(&Screen::new(1, 1), &Screen::new_fill(1, 1, pixel::pxl('#')));
+    }
+
+    #[test]
+    fn snapshot_file() {
+        let path = std::env::temp_dir()
+            .join(format!("console_engine_snapshot_{}", std::process::id()))
+            .join("screen.snap");
+        let mut screen = Screen::new(5, 1);
+        screen.print_fbg(0, 0, "Hello", Color::Green, Color::Reset);
+
+        // a missing snapshot fails without being written
+        let result = std::panic::catch_unwind(|| super::assert_snapshot_file(&path, &screen));
+        assert!(result.is_err());
+        assert!(!path.exists());
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, screen.to_annotated()).unwrap();
+        super::assert_snapshot_file(&path, &screen);
+
+        screen.set_pxl(0, 0, pixel::pxl('J'));
+        let result = std::panic::catch_unwind(|| super::assert_snapshot_file(&path, &screen));
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+        assert!(result.is_err());
+    }
+}
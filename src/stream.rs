@@ -0,0 +1,145 @@
+//! Async integration of the engine (requires feature `async`)
+//!
+//! The [EventStream] yields the same events as [ConsoleEngine::poll](../struct.ConsoleEngine.html#method.poll)
+//! without blocking the thread, so the engine can run next to other tasks in any async runtime.
+//! Frames are driven by a runtime independent timer.
+
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use futures_io::AsyncWrite;
+use futures_timer::Delay;
+
+use crate::backend::Backend;
+use crate::events::Event;
+use crate::ConsoleEngine;
+
+/// Stream of the events of an engine
+///
+/// Get one with [ConsoleEngine::event_stream](../struct.ConsoleEngine.html#method.event_stream).
+/// The stream never ends.
+///
+/// usage:
+/// ```
+/// use futures_util::StreamExt;
+///
+/// let mut stdout = async_std::io::stdout();
+/// loop {
+///     let event = engine.event_stream().next().await.unwrap();
+///     match event {
+///         Event::Frame => {
+///             engine.clear_screen();
+///             engine.print(0, 0, &status);
+///             engine.draw_async(&mut stdout).await?;
+///         }
+///         Event::Key(key) if key.code == KeyCode::Char('q') => break,
+///         _ => {}
+///     }
+/// }
+/// ```
+pub struct EventStream<'a, B: Backend> {
+    engine: &'a mut ConsoleEngine<B>,
+    timer: Option<Delay>,
+}
+
+impl<'a, B: Backend> EventStream<'a, B> {
+    pub(crate) fn new(engine: &'a mut ConsoleEngine<B>) -> Self {
+        Self {
+            engine,
+            timer: None,
+        }
+    }
+
+    /// Get the engine the events come from
+    pub fn engine(&mut self) -> &mut ConsoleEngine<B> {
+        self.engine
+    }
+}
+
+impl<B: Backend> Stream for EventStream<'_, B> {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Event>> {
+        let this = &mut *self;
+        // register first, so a message sent in between can't be missed
+        this.engine.input.register_waker(cx.waker());
+        if let Ok(Some(message)) = this.engine.next_input(std::time::Duration::ZERO) {
+            return Poll::Ready(Some(this.engine.message_to_event(message)));
+        }
+        loop {
+            if let Some(id) = this.engine.next_timer() {
+                return Poll::Ready(Some(Event::Timer(id)));
+            }
+            let elapsed_time = this.engine.instant.elapsed();
+            if this.engine.is_frame_due(elapsed_time, false) {
+                this.engine.next_frame();
+                return Poll::Ready(Some(Event::Frame));
+            }
+            // wait until the next frame or timer, a message wakes the stream up sooner
+            let timeout = this.engine.poll_timeout(elapsed_time, false);
+            let timer = this.timer.get_or_insert_with(|| Delay::new(timeout));
+            timer.reset(timeout);
+            if Pin::new(timer).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+/// Writes the whole buffer into an async output, then flushes it
+pub(crate) async fn write_all<W: AsyncWrite + Unpin>(
+    output: &mut W,
+    mut buffer: &[u8],
+) -> std::io::Result<()> {
+    while !buffer.is_empty() {
+        let written = poll_fn(|cx| Pin::new(&mut *output).poll_write(cx, buffer)).await?;
+        if written == 0 {
+            return Err(std::io::ErrorKind::WriteZero.into());
+        }
+        buffer = &buffer[written..];
+    }
+    poll_fn(|cx| Pin::new(&mut *output).poll_flush(cx)).await
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::KeyCode;
+    use futures_executor::block_on;
+    use futures_util::StreamExt;
+
+    use crate::backend::HeadlessBackend;
+    use crate::events::Event;
+    use crate::ConsoleEngine;
+
+    #[test]
+    fn event_stream() {
+        let mut engine =
+            ConsoleEngine::init_with_backend(HeadlessBackend::new(10, 2), 10, 2, 100).unwrap();
+        block_on(async {
+            let mut stream = engine.event_stream();
+            // the timer produces the frames
+            assert!(matches!(stream.next().await, Some(Event::Frame)));
+            // terminal input goes through the input thread
+            stream.engine().backend_mut().push_key(KeyCode::Enter);
+            assert!(
+                matches!(stream.next().await, Some(Event::Key(key)) if key.code == KeyCode::Enter)
+            );
+
+            // custom events sent from another thread wake the stream up
+            let sender = stream.engine().event_sender();
+            std::thread::spawn(move || sender.send(7u8).unwrap());
+            assert!(
+                matches!(stream.next().await, Some(Event::User(event)) if event.downcast_ref() == Some(&7u8))
+            );
+            assert!(matches!(stream.next().await, Some(Event::Frame)));
+
+            let mut output = vec![];
+            engine.print(0, 0, "async");
+            engine.draw_async(&mut output).await.unwrap();
+            assert!(String::from_utf8_lossy(&output).contains("async"));
+        });
+        assert_eq!(engine.backend().frames().len(), 1);
+    }
+}
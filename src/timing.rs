@@ -0,0 +1,319 @@
+//! Frame timing tools
+//!
+//! [FrameStats] measures the frames of a ConsoleEngine, [FixedTimestep] runs
+//! updates at a fixed rate, independently from the rate at which the screen is drawn,
+//! and [TimerHandle] refers to a timer scheduled on the engine.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Amount of frames used to compute the average FPS
+const FPS_WINDOW: usize = 60;
+
+/// Timing statistics about the last frames
+///
+/// The engine keeps one up to date, see [ConsoleEngine::get_frame_stats](../struct.ConsoleEngine.html#method.get_frame_stats)
+#[derive(Clone, Debug, Default)]
+pub struct FrameStats {
+    delta_time: Duration,
+    draw_time: Duration,
+    draw_size: usize,
+    frame_times: VecDeque<Duration>,
+    total_time: Duration,
+}
+
+impl FrameStats {
+    /// Creates empty statistics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the duration of a new frame
+    pub fn push_frame(&mut self, delta_time: Duration) {
+        self.delta_time = delta_time;
+        self.frame_times.push_back(delta_time);
+        self.total_time += delta_time;
+        if self.frame_times.len() > FPS_WINDOW {
+            if let Some(oldest) = self.frame_times.pop_front() {
+                self.total_time -= oldest;
+            }
+        }
+    }
+
+    /// Registers the time spent drawing the last frame
+    pub fn push_draw_time(&mut self, draw_time: Duration) {
+        self.draw_time = draw_time;
+    }
+
+    /// Registers the amount of bytes written by the last frame
+    pub fn push_draw_size(&mut self, draw_size: usize) {
+        self.draw_size = draw_size;
+    }
+
+    /// Time elapsed between the start of the last frame and the start of the previous one
+    pub fn get_delta_time(&self) -> Duration {
+        self.delta_time
+    }
+
+    /// Time spent in the last `draw` call
+    pub fn get_draw_time(&self) -> Duration {
+        self.draw_time
+    }
+
+    /// Bytes written to the terminal by the last `draw` call
+    pub fn get_draw_size(&self) -> usize {
+        self.draw_size
+    }
+
+    /// Average frames per second over the last 60 frames, `0.0` until a frame has passed
+    pub fn get_fps(&self) -> f64 {
+        if self.total_time.is_zero() {
+            return 0.0;
+        }
+        self.frame_times.len() as f64 / self.total_time.as_secs_f64()
+    }
+}
+
+/// Runs updates at a fixed rate, whatever the time a frame takes
+///
+/// Each frame, the elapsed time is added to an accumulator, which is then consumed one fixed step at a time.
+/// The remaining time gives an interpolation factor between the previous and the current update, to draw smooth movements.
+///
+/// To avoid falling behind forever after a long frame, at most a quarter of a second is accumulated per frame.
+///
+/// usage:
+/// ```
+/// use console_engine::timing::FixedTimestep;
+/// use std::time::Duration;
+///
+/// // run the physics 50 times per second
+/// let mut timestep = FixedTimestep::new(Duration::from_millis(20));
+/// loop {
+///     engine.wait_frame();
+///     timestep.accumulate(engine.get_delta_time());
+///     while timestep.step() {
+///         previous_position = position;
+///         position += speed * timestep.get_step().as_secs_f64();
+///     }
+///     // draw between the last two updates
+///     let alpha = timestep.get_alpha();
+///     let x = previous_position * (1.0 - alpha) + position * alpha;
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedTimestep {
+    step: Duration,
+    accumulator: Duration,
+    max_frame_time: Duration,
+}
+
+impl FixedTimestep {
+    /// Creates a timestep running an update every `step`
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "The step needs to be greater than zero.");
+        Self {
+            step,
+            accumulator: Duration::ZERO,
+            max_frame_time: Duration::from_millis(250),
+        }
+    }
+
+    /// Creates a timestep running the given amount of updates per second
+    pub fn from_rate(updates_per_second: u32) -> Self {
+        assert!(
+            updates_per_second > 0,
+            "Updates per second needs to be greater than zero."
+        );
+        Self::new(Duration::from_secs(1) / updates_per_second)
+    }
+
+    /// Get the duration of an update
+    pub fn get_step(&self) -> Duration {
+        self.step
+    }
+
+    /// Set the maximum time accumulated by a single frame (250ms by default)
+    pub fn set_max_frame_time(&mut self, max_frame_time: Duration) {
+        self.max_frame_time = max_frame_time;
+    }
+
+    /// Adds the time elapsed during a frame
+    pub fn accumulate(&mut self, delta_time: Duration) {
+        self.accumulator += delta_time.min(self.max_frame_time);
+    }
+
+    /// Consumes one step of the accumulated time.
+    /// Returns `false` when there isn't enough time left for an update
+    pub fn step(&mut self) -> bool {
+        if self.accumulator >= self.step {
+            self.accumulator -= self.step;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Interpolation factor between the previous update (`0.0`) and the last one (`1.0`)
+    pub fn get_alpha(&self) -> f64 {
+        self.accumulator.as_secs_f64() / self.step.as_secs_f64()
+    }
+
+    /// Discards the accumulated time
+    pub fn reset(&mut self) {
+        self.accumulator = Duration::ZERO;
+    }
+}
+
+/// Handle of a timer scheduled on a ConsoleEngine, used to cancel it
+///
+/// see [ConsoleEngine::set_timeout](../struct.ConsoleEngine.html#method.set_timeout)
+/// and [ConsoleEngine::set_interval](../struct.ConsoleEngine.html#method.set_interval)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimerHandle(u64);
+
+/// A scheduled timer
+#[derive(Clone, Copy, Debug)]
+struct Timer {
+    handle: TimerHandle,
+    id: u32,
+    deadline: Instant,
+    interval: Option<Duration>,
+}
+
+/// Timers scheduled on an engine
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Timers {
+    timers: Vec<Timer>,
+    next_handle: u64,
+}
+
+impl Timers {
+    /// Schedules a timer firing at the given deadline, then every `interval` if any
+    pub(crate) fn add(
+        &mut self,
+        id: u32,
+        deadline: Instant,
+        interval: Option<Duration>,
+    ) -> TimerHandle {
+        let handle = TimerHandle(self.next_handle);
+        self.next_handle += 1;
+        self.timers.push(Timer {
+            handle,
+            id,
+            deadline,
+            interval,
+        });
+        handle
+    }
+
+    /// Removes a timer, returns `false` if it already fired or has been cancelled
+    pub(crate) fn cancel(&mut self, handle: TimerHandle) -> bool {
+        let count = self.timers.len();
+        self.timers.retain(|timer| timer.handle != handle);
+        self.timers.len() != count
+    }
+
+    /// Checks whenever a timer is still scheduled
+    pub(crate) fn is_active(&self, handle: TimerHandle) -> bool {
+        self.timers.iter().any(|timer| timer.handle == handle)
+    }
+
+    /// Get the moment the next timer fires
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.timers.iter().map(|timer| timer.deadline).min()
+    }
+
+    /// Takes the id of the earliest timer due at the given moment.
+    /// Intervals are rescheduled, skipping the periods that were missed
+    pub(crate) fn pop_due(&mut self, now: Instant) -> Option<u32> {
+        let (index, _) = self
+            .timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.deadline <= now)
+            .min_by_key(|(_, timer)| timer.deadline)?;
+        let timer = &mut self.timers[index];
+        let id = timer.id;
+        match timer.interval {
+            Some(interval) => {
+                while timer.deadline <= now {
+                    timer.deadline += interval;
+                }
+            }
+            None => {
+                self.timers.remove(index);
+            }
+        }
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{FixedTimestep, FrameStats, Timers};
+
+    #[test]
+    fn frame_stats() {
+        let mut stats = FrameStats::new();
+        assert_eq!(stats.get_fps(), 0.0);
+        for _ in 0..100 {
+            stats.push_frame(Duration::from_millis(50));
+        }
+        stats.push_frame(Duration::from_millis(20));
+        assert_eq!(stats.get_delta_time(), Duration::from_millis(20));
+        // 59 frames of 50ms and one of 20ms
+        assert!((stats.get_fps() - 60.0 / 2.97).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fixed_timestep() {
+        let mut timestep = FixedTimestep::from_rate(50);
+        timestep.accumulate(Duration::from_millis(50));
+        let mut steps = 0;
+        while timestep.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 2);
+        assert!((timestep.get_alpha() - 0.5).abs() < 1e-9);
+
+        // a long frame only accumulates 250ms
+        timestep.reset();
+        timestep.accumulate(Duration::from_secs(3));
+        let mut steps = 0;
+        while timestep.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, 12);
+    }
+
+    #[test]
+    fn timers() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut timers = Timers::default();
+        let timeout = timers.add(1, start + ms(30), None);
+        let interval = timers.add(2, start + ms(20), Some(ms(20)));
+        let cancelled = timers.add(3, start + ms(10), None);
+        assert!(timers.cancel(cancelled));
+        assert!(!timers.cancel(cancelled));
+        assert_eq!(timers.next_deadline(), Some(start + ms(20)));
+
+        assert_eq!(timers.pop_due(start + ms(10)), None);
+        assert_eq!(timers.pop_due(start + ms(20)), Some(2));
+        assert_eq!(timers.pop_due(start + ms(20)), None);
+        // the earliest timer fires first
+        assert_eq!(timers.pop_due(start + ms(45)), Some(1));
+        assert_eq!(timers.pop_due(start + ms(45)), Some(2));
+        assert_eq!(timers.pop_due(start + ms(45)), None);
+        assert!(!timers.is_active(timeout));
+        // missed periods are skipped
+        assert_eq!(timers.pop_due(start + ms(130)), Some(2));
+        assert_eq!(timers.pop_due(start + ms(130)), None);
+        assert_eq!(timers.next_deadline(), Some(start + ms(140)));
+        assert!(timers.cancel(interval));
+        assert_eq!(timers.next_deadline(), None);
+    }
+}
@@ -0,0 +1,384 @@
+//! Clipped views on Screens
+//!
+//! A [ScreenView] borrows a region of a [Screen] and exposes the same drawing functions,
+//! using coordinates relative to the region and clipping everything to its bounds.
+//! Views can be created from a Screen, from the [ConsoleEngine](../struct.ConsoleEngine.html#method.view) or from another view.
+//!
+//! usage:
+//! ```
+//! use console_engine::rect_style::BorderStyle;
+//!
+//! let mut sidebar = engine.view(0, 0, 20, 10);
+//! sidebar.rect_border(0, 0, 19, 9, BorderStyle::new_light());
+//! // this text is cut at the right border of the sidebar
+//! sidebar.print(1, 1, "A very long title that doesn't fit");
+//!
+//! // nested views are clipped to their parent
+//! let mut inner = sidebar.view(1, 2, 18, 7);
+//! inner.print(0, 0, "Inside the border");
+//! ```
+
+use crossterm::style::{Attributes, Color};
+
+use crate::color::{Gradient, GradientDirection};
+use crate::error::Error;
+use crate::layout::Rect;
+use crate::pixel::{self, Pixel};
+use crate::rect_style::BorderStyle;
+use crate::screen::Screen;
+
+/// Mutable view on a region of a Screen
+///
+/// Coordinates are relative to the top left corner of the view,
+/// and nothing is drawn outside of the view (or outside of its parent views).
+pub struct ScreenView<'a> {
+    screen: &'a mut Screen,
+    // position of the top left corner of the view on the screen
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    // visible area, in screen coordinates
+    clip: Rect,
+}
+
+impl<'a> ScreenView<'a> {
+    pub(crate) fn new(screen: &'a mut Screen, x: i32, y: i32, width: u32, height: u32) -> Self {
+        let clip = screen
+            .get_area()
+            .intersection(Rect::new(x, y, width, height));
+        Self {
+            screen,
+            x,
+            y,
+            width,
+            height,
+            clip,
+        }
+    }
+
+    /// Creates a view on a region of this view, the coordinates being relative to this view
+    ///
+    /// The new view is clipped to the bounds of this view
+    pub fn view(&mut self, x: i32, y: i32, width: u32, height: u32) -> ScreenView<'_> {
+        let (x, y) = (self.x + x, self.y + y);
+        let clip = self.clip.intersection(Rect::new(x, y, width, height));
+        ScreenView {
+            screen: self.screen,
+            x,
+            y,
+            width,
+            height,
+            clip,
+        }
+    }
+
+    /// Get the view width
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the view height
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the area covered by the view, in local coordinates
+    pub fn get_area(&self) -> Rect {
+        Rect::new(0, 0, self.width, self.height)
+    }
+
+    /// Get the position of the view on the underlying screen
+    pub fn get_position(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+
+    /// Reset the view to a blank state
+    pub fn clear(&mut self) {
+        self.fill(pixel::pxl(' '));
+    }
+
+    /// Fill the entire view with the given pixel
+    pub fn fill(&mut self, pixel: Pixel) {
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                self.set_pxl(x, y, pixel);
+            }
+        }
+    }
+
+    /// prints a string at the specified coordinates
+    ///
+    /// see [Screen::print](../screen/struct.Screen.html#method.print)
+    pub fn print(&mut self, x: i32, y: i32, string: &str) {
+        self.print_fbg(x, y, string, Color::Reset, Color::Reset)
+    }
+
+    /// prints a string at the specified coordinates with the specified foreground and background color
+    ///
+    /// see [Screen::print_fbg](../screen/struct.Screen.html#method.print_fbg)
+    pub fn print_fbg(&mut self, x: i32, y: i32, string: &str, fg: Color, bg: Color) {
+        self.print_fbg_attr(x, y, string, fg, bg, Attributes::default())
+    }
+
+    /// prints a string at the specified coordinates with the specified text attributes
+    ///
+    /// see [Screen::print_attr](../screen/struct.Screen.html#method.print_attr)
+    pub fn print_attr(&mut self, x: i32, y: i32, string: &str, attr: Attributes) {
+        self.print_fbg_attr(x, y, string, Color::Reset, Color::Reset, attr)
+    }
+
+    /// prints a string at the specified coordinates with the specified foreground and background color and text attributes
+    ///
+    /// see [Screen::print_fbg_attr](../screen/struct.Screen.html#method.print_fbg_attr)
+    pub fn print_fbg_attr(
+        &mut self,
+        x: i32,
+        y: i32,
+        string: &str,
+        fg: Color,
+        bg: Color,
+        attr: Attributes,
+    ) {
+        // the text is laid out in the visible part of the view,
+        // so wide graphemes cut by a parent view are blanked like on the borders of a screen
+        let (offset_x, offset_y) = (self.clip.x - self.x, self.clip.y - self.y);
+        let (width, height) = (self.clip.width, self.clip.height);
+        crate::shapes::text(
+            x - offset_x,
+            y - offset_y,
+            string,
+            fg,
+            bg,
+            attr,
+            width,
+            height,
+            &mut |x, y, pixel| self.set_pxl(x + offset_x, y + offset_y, pixel),
+        );
+    }
+
+    /// Prints a screen on specified coordinates
+    ///
+    /// see [Screen::print_screen](../screen/struct.Screen.html#method.print_screen)
+    pub fn print_screen(&mut self, x: i32, y: i32, source: &Screen) {
+        for j in 0..source.get_height() as i32 {
+            for i in 0..source.get_width() as i32 {
+                // unwrap here because we are sure that we won't get out of range
+                self.set_pxl(x + i, y + j, source.get_pxl(i, j).unwrap());
+            }
+        }
+    }
+
+    /// Prints a screen on specified coordinates, ignoring a specific character while printing
+    ///
+    /// see [Screen::print_screen_alpha](../screen/struct.Screen.html#method.print_screen_alpha)
+    pub fn print_screen_alpha(&mut self, x: i32, y: i32, source: &Screen, alpha_character: char) {
+        for j in 0..source.get_height() as i32 {
+            for i in 0..source.get_width() as i32 {
+                // unwrap here because we are sure that we won't get out of range
+                let pxl = source.get_pxl(i, j).unwrap();
+                if pxl.chr != alpha_character {
+                    self.set_pxl(x + i, y + j, pxl);
+                }
+            }
+        }
+    }
+
+    /// Draws a horizontal line of the provided character
+    pub fn h_line(&mut self, start_x: i32, start_y: i32, end_x: i32, character: Pixel) {
+        self.line(start_x, start_y, end_x, start_y, character);
+    }
+
+    /// Draws a vertical line of the provided character
+    pub fn v_line(&mut self, start_x: i32, start_y: i32, end_y: i32, character: Pixel) {
+        self.line(start_x, start_y, start_x, end_y, character);
+    }
+
+    /// Draws a line of the provided character between two sets of coordinates
+    ///
+    /// see [Screen::line](../screen/struct.Screen.html#method.line)
+    pub fn line(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, character: Pixel) {
+        crate::shapes::line(start_x, start_y, end_x, end_y, &mut |x, y| {
+            self.set_pxl(x, y, character)
+        });
+    }
+
+    /// Draws a rectangle of the provided character between two sets of coordinates
+    pub fn rect(&mut self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, character: Pixel) {
+        self.h_line(start_x, start_y, end_x, character); // top
+        self.v_line(end_x, start_y, end_y, character); // right
+        self.h_line(end_x, end_y, start_x, character); // bottom
+        self.v_line(start_x, end_y, start_y, character); // left
+    }
+
+    /// Draws a rectangle with custom borders between two sets of coordinates
+    ///
+    /// see [Screen::rect_border](../screen/struct.Screen.html#method.rect_border)
+    pub fn rect_border(
+        &mut self,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        rect_style: BorderStyle,
+    ) {
+        self.h_line(start_x, start_y, end_x, rect_style.top_bottom); // top
+        self.v_line(end_x, start_y, end_y, rect_style.left_right); // right
+        self.h_line(end_x, end_y, start_x, rect_style.top_bottom); // bottom
+        self.v_line(start_x, end_y, start_y, rect_style.left_right); // left
+
+        // borders
+        self.set_pxl(start_x, start_y, rect_style.corner_top_left); // top left corner
+        self.set_pxl(end_x, start_y, rect_style.corner_top_right); // top right corner
+        self.set_pxl(start_x, end_y, rect_style.corner_bottom_left); // bottom left corner
+        self.set_pxl(end_x, end_y, rect_style.corner_bottom_right); // bottom right corner
+    }
+
+    /// Fill a rectangle of the provided character between two sets of coordinates
+    pub fn fill_rect(
+        &mut self,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        character: Pixel,
+    ) {
+        for y in start_y.min(end_y)..=start_y.max(end_y) {
+            self.h_line(start_x, y, end_x, character);
+        }
+    }
+
+    /// Fill a rectangle between two sets of coordinates with the colors of a gradient, as background of blank pixels
+    pub fn fill_gradient(
+        &mut self,
+        start_x: i32,
+        start_y: i32,
+        end_x: i32,
+        end_y: i32,
+        gradient: &Gradient,
+        direction: GradientDirection,
+    ) {
+        crate::shapes::gradient_rect(
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+            gradient,
+            direction,
+            &mut |x, y, color| self.set_pxl(x, y, pixel::pxl_bg(' ', color)),
+        );
+    }
+
+    /// Draws a circle of the provided character at an x and y position with a radius
+    pub fn circle(&mut self, x: i32, y: i32, radius: u32, character: Pixel) {
+        crate::shapes::circle(x, y, radius, &mut |x, y| self.set_pxl(x, y, character));
+    }
+
+    /// Fill a circle of the provided character at an x and y position with a radius
+    pub fn fill_circle(&mut self, x: i32, y: i32, radius: u32, character: Pixel) {
+        crate::shapes::fill_circle(x, y, radius, &mut |x, y| self.set_pxl(x, y, character));
+    }
+
+    /// Draws a triangle of the provided character using three sets of coordinates
+    #[allow(clippy::too_many_arguments)]
+    pub fn triangle(
+        &mut self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        x3: i32,
+        y3: i32,
+        character: Pixel,
+    ) {
+        self.line(x1, y1, x2, y2, character);
+        self.line(x2, y2, x3, y3, character);
+        self.line(x3, y3, x1, y1, character);
+    }
+
+    /// Fill a triangle of the provided character using three sets of coordinates
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_triangle(
+        &mut self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        x3: i32,
+        y3: i32,
+        character: Pixel,
+    ) {
+        self.triangle(x1, y1, x2, y2, x3, y3, character);
+        let (width, height) = (self.width, self.height);
+        crate::shapes::fill_triangle(x1, y1, x2, y2, x3, y3, width, height, &mut |x, y| {
+            self.set_pxl(x, y, character)
+        });
+    }
+
+    /// Sets the pixel at the given local coordinates, if it's visible
+    pub fn set_pxl(&mut self, x: i32, y: i32, character: Pixel) {
+        let (x, y) = (self.x + x, self.y + y);
+        if self.clip.contains(x, y) {
+            self.screen.set_pxl(x, y, character);
+        }
+    }
+
+    /// Get the pixel stored at the given local coordinates
+    ///
+    /// Returns an error if the coordinates are outside of the visible part of the view
+    pub fn get_pxl(&self, x: i32, y: i32) -> Result<Pixel, Error> {
+        let (screen_x, screen_y) = (self.x + x, self.y + y);
+        if self.clip.contains(screen_x, screen_y) {
+            return self.screen.get_pxl(screen_x, screen_y);
+        }
+        Err(Error::OutOfBounds {
+            x,
+            y,
+            width: self.width,
+            height: self.height,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{pixel, rect_style::BorderStyle, screen::Screen};
+
+    #[test]
+    fn clipping() {
+        let mut screen = Screen::new(8, 4);
+        {
+            let mut view = screen.view(2, 1, 4, 2);
+            assert_eq!((view.get_width(), view.get_height()), (4, 2));
+            view.print(-1, 0, "abcdef");
+            view.line(0, 1, 7, 1, pixel::pxl('-'));
+            assert_eq!(view.get_pxl(0, 0).unwrap().chr, 'b');
+            assert!(view.get_pxl(4, 0).is_err());
+        }
+        assert_eq!(screen.to_text(), "        \n  bcde  \n  ----  \n        ");
+
+        // views can be partially out of the screen
+        let mut view = screen.view(6, -1, 4, 4);
+        view.fill(pixel::pxl('#'));
+        assert_eq!(view.get_position(), (6, -1));
+        assert_eq!(screen.to_text(), "      ##\n  bcde##\n  ----##\n        ");
+    }
+
+    #[test]
+    fn nested() {
+        let mut screen = Screen::new(6, 4);
+        let mut outer = screen.view(1, 0, 4, 4);
+        {
+            // the inner view is larger than its parent
+            let mut inner = outer.view(1, 1, 5, 2);
+            inner.print(0, 0, "wide");
+            inner.print(0, 1, "汉字");
+        }
+        // the second grapheme doesn't fit in the outer view, and is blanked
+        assert_eq!(screen.to_text(), "      \n  wid \n  汉  \n      ");
+
+        let mut outer = screen.view(1, 0, 4, 4);
+        outer.rect_border(0, 0, 3, 3, BorderStyle::new_simple());
+        assert_eq!(screen.to_text(), " +--+ \n |wi| \n |汉| \n +--+ ");
+    }
+}
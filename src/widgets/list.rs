@@ -0,0 +1,266 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEventKind};
+
+use crate::{events::Event, pixel, screen::Screen};
+
+use super::{fit, local_mouse, Selection, Widget, WidgetStyle};
+
+/// Scrollable list of selectable items
+///
+/// Use the arrow keys, Page Up / Page Down and Home / End to move the selection while the list is active,
+/// click on an item to select it or use the mouse wheel to scroll.
+///
+/// usage:
+/// ```
+/// use console_engine::widgets::{ListView, Widget};
+///
+/// let mut list = ListView::new(20, 5, vec![String::from("first"), String::from("second")]);
+/// list.set_active(true);
+/// list.set_position(2, 2);
+///
+/// // in the main loop
+/// list.handle_event(event);
+/// engine.print_screen(2, 2, list.draw());
+/// ```
+pub struct ListView {
+    screen: Screen,
+    items: Vec<String>,
+    selection: Selection,
+    dirty: bool,
+    active: bool,
+    position: (i32, i32),
+    style: WidgetStyle,
+}
+
+impl ListView {
+    /// Creates a new list view, the first item being selected
+    pub fn new(w: u32, h: u32, items: Vec<String>) -> Self {
+        let mut selection = Selection::default();
+        selection.select(Some(0), items.len(), h as usize);
+        Self {
+            screen: Screen::new(w, h),
+            items,
+            selection,
+            dirty: true,
+            active: false,
+            position: (0, 0),
+            style: WidgetStyle::default(),
+        }
+    }
+
+    /// Retrieve the stored list of items
+    pub fn get_items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    /// Sets a list of items, keeping the selection if possible
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        let selected = self
+            .selection
+            .selected
+            .map(|s| s.min(self.items.len().saturating_sub(1)))
+            .or(Some(0));
+        self.select(selected);
+    }
+
+    /// Index of the selected item, if any
+    pub fn get_selected(&self) -> Option<usize> {
+        self.selection.selected
+    }
+
+    /// The selected item, if any
+    pub fn get_selected_item(&self) -> Option<&String> {
+        self.selection.selected.and_then(|s| self.items.get(s))
+    }
+
+    /// Selects an item and scrolls to it
+    pub fn select(&mut self, index: Option<usize>) {
+        self.dirty = true;
+        self.selection
+            .select(index, self.items.len(), self.get_height() as usize);
+    }
+
+    /// Moves the selection up (negative) or down (positive)
+    ///
+    /// The selection is clamped at its boundaries
+    pub fn move_selection(&mut self, amount: i32) {
+        self.dirty = true;
+        self.selection
+            .move_by(amount as i64, self.items.len(), self.get_height() as usize);
+    }
+
+    /// Scrolls the list up (negative) or down (positive) without changing the selection
+    pub fn scroll(&mut self, amount: i32) {
+        self.dirty = true;
+        self.selection
+            .scroll(amount as i64, self.items.len(), self.get_height() as usize);
+    }
+
+    /// Index of the first visible item
+    pub fn get_offset(&self) -> usize {
+        self.selection.offset
+    }
+
+    /// Changes the colors of the list
+    pub fn set_style(&mut self, style: WidgetStyle) {
+        self.dirty = true;
+        self.style = style;
+    }
+}
+
+impl Widget for ListView {
+    fn get_width(&self) -> u32 {
+        self.screen.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.screen.get_height()
+    }
+
+    fn resize(&mut self, w: u32, h: u32) {
+        self.screen.resize(w, h);
+        self.select(self.selection.selected);
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        let page = self.get_height().max(1) as i32;
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) if self.active => match code {
+                KeyCode::Up => self.move_selection(-1),
+                KeyCode::Down => self.move_selection(1),
+                KeyCode::PageUp => self.move_selection(-page),
+                KeyCode::PageDown => self.move_selection(page),
+                KeyCode::Home => self.move_selection(i32::MIN),
+                KeyCode::End => self.move_selection(i32::MAX),
+                _ => {}
+            },
+            Event::Mouse(mouse) => {
+                if local_mouse(&mouse, self.position, self.get_width(), self.get_height()).is_none()
+                {
+                    return;
+                }
+                match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        let row = mouse.row as i32 - self.position.1;
+                        let index = self.selection.offset + row as usize;
+                        if index < self.items.len() {
+                            self.select(Some(index));
+                        }
+                    }
+                    MouseEventKind::ScrollUp => self.scroll(-1),
+                    MouseEventKind::ScrollDown => self.scroll(1),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.dirty = true;
+        self.active = active;
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn set_position(&mut self, x: i32, y: i32) {
+        self.position = (x, y);
+    }
+
+    fn draw(&mut self) -> &Screen {
+        if self.dirty {
+            self.dirty = false;
+            self.screen
+                .fill(pixel::pxl_fbg(' ', self.style.fg, self.style.bg));
+            let width = self.get_width() as usize;
+            for (row, (id, item)) in self
+                .items
+                .iter()
+                .enumerate()
+                .skip(self.selection.offset)
+                .take(self.get_height() as usize)
+                .enumerate()
+            {
+                let (fg, bg) = if self.selection.selected == Some(id) {
+                    (self.style.highlight_fg, self.style.highlight_bg)
+                } else {
+                    (self.style.fg, self.style.bg)
+                };
+                self.screen
+                    .print_fbg(0, row as i32, &fit(item, width), fg, bg);
+            }
+        }
+        &self.screen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+
+    use super::ListView;
+    use crate::events::Event;
+    use crate::widgets::Widget;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn scrolling() {
+        let items = (0..10).map(|i| format!("item {}", i)).collect();
+        let mut list = ListView::new(6, 3, items);
+        assert_eq!(list.draw().to_text(), "item 0\nitem 1\nitem 2");
+
+        // inactive lists ignore the keyboard
+        list.handle_event(key(KeyCode::Down));
+        assert_eq!(list.get_selected(), Some(0));
+
+        list.set_active(true);
+        for _ in 0..3 {
+            list.handle_event(key(KeyCode::Down));
+        }
+        assert_eq!(list.get_selected_item().unwrap(), "item 3");
+        assert_eq!(list.get_offset(), 1);
+        assert_eq!(list.draw().to_text(), "item 1\nitem 2\nitem 3");
+
+        list.handle_event(key(KeyCode::End));
+        assert_eq!((list.get_selected(), list.get_offset()), (Some(9), 7));
+        list.handle_event(key(KeyCode::PageUp));
+        assert_eq!((list.get_selected(), list.get_offset()), (Some(6), 6));
+        list.handle_event(key(KeyCode::Home));
+        assert_eq!((list.get_selected(), list.get_offset()), (Some(0), 0));
+    }
+
+    #[test]
+    fn mouse() {
+        let items = (0..10).map(|i| format!("item {}", i)).collect();
+        let mut list = ListView::new(6, 3, items);
+        list.set_position(2, 1);
+        let mouse = |kind, column, row| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        list.handle_event(mouse(MouseEventKind::ScrollDown, 3, 1));
+        list.handle_event(mouse(
+            MouseEventKind::Down(crossterm::event::MouseButton::Left),
+            3,
+            3,
+        ));
+        assert_eq!((list.get_selected(), list.get_offset()), (Some(3), 1));
+
+        // outside of the list
+        list.handle_event(mouse(MouseEventKind::ScrollDown, 0, 1));
+        assert_eq!(list.get_offset(), 1);
+    }
+}
@@ -0,0 +1,160 @@
+//! Reusable display widgets
+//!
+//! Widgets are stateful components that build themselves as a [Screen],
+//! which can then be printed anywhere with [print_screen](../struct.ConsoleEngine.html#method.print_screen).
+//! Interactive widgets handle keyboard and mouse input through [Event]s, in the same way as form fields.
+//!
+//! see example `widgets`
+use crossterm::event::MouseEvent;
+use crossterm::style::Color;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{events::Event, screen::Screen};
+
+mod list;
+mod progress;
+mod sparkline;
+mod table;
+mod tabs;
+
+pub use list::ListView;
+pub use progress::Gauge;
+pub use progress::ProgressBar;
+pub use sparkline::Sparkline;
+pub use table::Table;
+pub use tabs::Tabs;
+
+/// Necessary functions to build a Widget
+pub trait Widget {
+    /// Get the width of the widget
+    fn get_width(&self) -> u32;
+    /// Get the height of the widget
+    fn get_height(&self) -> u32;
+    /// Resize the widget
+    fn resize(&mut self, w: u32, h: u32);
+
+    /// Let the widget handle a ConsoleEngine Event.
+    ///
+    /// Keyboard events are only handled by active widgets,
+    /// mouse events are handled according to the position given by [set_position](#method.set_position).
+    /// Widgets that only display data ignore every event.
+    fn handle_event(&mut self, _event: Event) {}
+
+    /// Set the active state of a widget (if applicable)
+    fn set_active(&mut self, _active: bool) {}
+    /// Checks if the state of a widget is active
+    fn is_active(&self) -> bool {
+        false
+    }
+
+    /// Tells the widget where it is printed on the terminal, so it can handle mouse events
+    fn set_position(&mut self, _x: i32, _y: i32) {}
+
+    /// Asks the widget to build itself as a `Screen`
+    fn draw(&mut self) -> &Screen;
+}
+
+/// Colors used by widgets
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WidgetStyle {
+    /// Foreground Color
+    pub fg: Color,
+    /// Background Color
+    pub bg: Color,
+    /// Foreground Color of highlighted elements (selected items, filled parts, ...)
+    pub highlight_fg: Color,
+    /// Background Color of highlighted elements (selected items, filled parts, ...)
+    pub highlight_bg: Color,
+}
+
+impl Default for WidgetStyle {
+    fn default() -> Self {
+        Self {
+            fg: Color::Reset,
+            bg: Color::Reset,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::Grey,
+        }
+    }
+}
+
+/// Converts the terminal coordinates of a mouse event into coordinates relative to a widget
+///
+/// Returns `None` if the event happened outside of the widget
+pub(crate) fn local_mouse(
+    event: &MouseEvent,
+    position: (i32, i32),
+    width: u32,
+    height: u32,
+) -> Option<(u32, u32)> {
+    let x = event.column as i32 - position.0;
+    let y = event.row as i32 - position.1;
+    if x >= 0 && y >= 0 && x < width as i32 && y < height as i32 {
+        Some((x as u32, y as u32))
+    } else {
+        None
+    }
+}
+
+/// Selected item and scrolling offset of a scrollable list of rows
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Selection {
+    pub selected: Option<usize>,
+    pub offset: usize,
+}
+
+impl Selection {
+    /// Moves the selection up (negative) or down (positive), clamped at the boundaries
+    pub fn move_by(&mut self, amount: i64, len: usize, visible: usize) {
+        if len == 0 {
+            self.selected = None;
+            return;
+        }
+        let current = self.selected.map(|s| s as i64).unwrap_or(-1);
+        self.select(
+            Some((current + amount).clamp(0, len as i64 - 1) as usize),
+            len,
+            visible,
+        );
+    }
+
+    /// Selects an item, and scrolls to make it visible
+    pub fn select(&mut self, index: Option<usize>, len: usize, visible: usize) {
+        self.selected = index.filter(|i| *i < len);
+        if let Some(selected) = self.selected {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if visible > 0 && selected >= self.offset + visible {
+                self.offset = selected + 1 - visible;
+            }
+        }
+        self.clamp_offset(len, visible);
+    }
+
+    /// Scrolls the rows without changing the selection
+    pub fn scroll(&mut self, amount: i64, len: usize, visible: usize) {
+        self.offset = (self.offset as i64 + amount).max(0) as usize;
+        self.clamp_offset(len, visible);
+    }
+
+    fn clamp_offset(&mut self, len: usize, visible: usize) {
+        self.offset = self.offset.min(len.saturating_sub(visible));
+    }
+}
+
+/// Cuts a string so it fits in the given width, padding it with spaces if it's too short
+pub(crate) fn fit(string: &str, width: usize) -> String {
+    let mut output = String::new();
+    let mut output_width = 0;
+    for grapheme in string.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        if output_width + grapheme_width > width {
+            break;
+        }
+        output.push_str(grapheme);
+        output_width += grapheme_width;
+    }
+    output.push_str(&" ".repeat(width - output_width));
+    output
+}
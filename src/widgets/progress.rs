@@ -0,0 +1,276 @@
+use crossterm::style::Color;
+
+use crate::{pixel, screen::Screen};
+
+use super::{Widget, WidgetStyle};
+
+/// Characters used to draw a fraction of a cell, from 1/8 to 8/8
+const EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+/// Clamps a progress value between 0 and 1
+fn clamp_ratio(ratio: f32) -> f32 {
+    if ratio.is_nan() {
+        0.0
+    } else {
+        ratio.clamp(0.0, 1.0)
+    }
+}
+
+/// One line progress bar
+///
+/// The bar is drawn with a precision of an eighth of a cell,
+/// and can display the percentage on its right.
+///
+/// usage:
+/// ```
+/// use console_engine::widgets::{ProgressBar, Widget};
+///
+/// let mut bar = ProgressBar::new(20);
+/// bar.set_progress(0.42);
+///
+/// engine.print_screen(0, 0, bar.draw());
+/// ```
+pub struct ProgressBar {
+    screen: Screen,
+    progress: f32,
+    show_percentage: bool,
+    dirty: bool,
+    style: WidgetStyle,
+}
+
+impl ProgressBar {
+    /// Creates a new progress bar of the given width, empty and showing its percentage
+    pub fn new(w: u32) -> Self {
+        Self {
+            screen: Screen::new(w, 1),
+            progress: 0.0,
+            show_percentage: true,
+            dirty: true,
+            style: WidgetStyle::default(),
+        }
+    }
+
+    /// Get the progress, between 0 and 1
+    pub fn get_progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Sets the progress, the value is clamped between 0 and 1
+    pub fn set_progress(&mut self, progress: f32) {
+        self.dirty = true;
+        self.progress = clamp_ratio(progress);
+    }
+
+    /// Shows or hides the percentage on the right of the bar
+    pub fn show_percentage(&mut self, show: bool) {
+        self.dirty = true;
+        self.show_percentage = show;
+    }
+
+    /// Changes the colors of the bar, the filled part uses the highlight background as its color
+    pub fn set_style(&mut self, style: WidgetStyle) {
+        self.dirty = true;
+        self.style = style;
+    }
+}
+
+impl Widget for ProgressBar {
+    fn get_width(&self) -> u32 {
+        self.screen.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.screen.get_height()
+    }
+
+    fn resize(&mut self, w: u32, _: u32) {
+        self.screen.resize(w, 1);
+        self.dirty = true;
+    }
+
+    fn draw(&mut self) -> &Screen {
+        if self.dirty {
+            self.dirty = false;
+            self.screen
+                .fill(pixel::pxl_fbg(' ', self.style.fg, self.style.bg));
+            let mut width = self.get_width();
+            if self.show_percentage {
+                let percentage = format!("{:>4}%", (self.progress * 100.0).round() as u32);
+                width = width.saturating_sub(percentage.len() as u32);
+                self.screen
+                    .print_fbg(width as i32, 0, &percentage, self.style.fg, self.style.bg);
+            }
+            let eighths = (self.progress * width as f32 * 8.0).round() as u32;
+            let bar_color = self.style.highlight_bg;
+            for x in 0..eighths / 8 {
+                self.screen
+                    .set_pxl(x as i32, 0, pixel::pxl_fbg('█', bar_color, self.style.bg));
+            }
+            if eighths % 8 != 0 {
+                self.screen.set_pxl(
+                    (eighths / 8) as i32,
+                    0,
+                    pixel::pxl_fbg(
+                        EIGHTHS[(eighths % 8) as usize - 1],
+                        bar_color,
+                        self.style.bg,
+                    ),
+                );
+            }
+        }
+        &self.screen
+    }
+}
+
+/// Block filled according to a ratio, with a centered label
+///
+/// The filled part uses the highlight colors of the style.
+/// By default the label is the percentage of the ratio.
+///
+/// usage:
+/// ```
+/// use console_engine::widgets::{Gauge, Widget};
+///
+/// let mut gauge = Gauge::new(20, 3);
+/// gauge.set_ratio(0.7);
+/// gauge.set_label(Some(String::from("7 / 10")));
+///
+/// engine.print_screen(0, 0, gauge.draw());
+/// ```
+pub struct Gauge {
+    screen: Screen,
+    ratio: f32,
+    label: Option<String>,
+    dirty: bool,
+    style: WidgetStyle,
+}
+
+impl Gauge {
+    /// Creates a new gauge of the given size, empty and without label
+    pub fn new(w: u32, h: u32) -> Self {
+        Self {
+            screen: Screen::new(w, h),
+            ratio: 0.0,
+            label: None,
+            dirty: true,
+            style: WidgetStyle::default(),
+        }
+    }
+
+    /// Get the ratio, between 0 and 1
+    pub fn get_ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets the ratio, the value is clamped between 0 and 1
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.dirty = true;
+        self.ratio = clamp_ratio(ratio);
+    }
+
+    /// Sets a custom label, or use the percentage with `None`
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.dirty = true;
+        self.label = label;
+    }
+
+    /// Changes the colors of the gauge
+    pub fn set_style(&mut self, style: WidgetStyle) {
+        self.dirty = true;
+        self.style = style;
+    }
+}
+
+impl Widget for Gauge {
+    fn get_width(&self) -> u32 {
+        self.screen.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.screen.get_height()
+    }
+
+    fn resize(&mut self, w: u32, h: u32) {
+        self.screen.resize(w, h);
+        self.dirty = true;
+    }
+
+    fn draw(&mut self) -> &Screen {
+        if self.dirty {
+            self.dirty = false;
+            let width = self.get_width();
+            let filled = (self.ratio * width as f32).round() as i32;
+            self.screen
+                .fill(pixel::pxl_fbg(' ', self.style.fg, self.style.bg));
+            if filled > 0 {
+                self.screen.fill_rect(
+                    0,
+                    0,
+                    filled - 1,
+                    self.get_height() as i32 - 1,
+                    pixel::pxl_fbg(' ', self.style.highlight_fg, self.style.highlight_bg),
+                );
+            }
+            let label = self
+                .label
+                .clone()
+                .unwrap_or_else(|| format!("{}%", (self.ratio * 100.0).round() as u32));
+            let label_x = (width as i32 - label.chars().count() as i32) / 2;
+            let label_y = (self.get_height() as i32 - 1) / 2;
+            for (i, chr) in label.chars().enumerate() {
+                let x = label_x + i as i32;
+                let (fg, bg): (Color, Color) = if x < filled {
+                    (self.style.highlight_fg, self.style.highlight_bg)
+                } else {
+                    (self.style.fg, self.style.bg)
+                };
+                self.screen.set_pxl(x, label_y, pixel::pxl_fbg(chr, fg, bg));
+            }
+        }
+        &self.screen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::Color;
+
+    use super::{Gauge, ProgressBar};
+    use crate::widgets::{Widget, WidgetStyle};
+
+    #[test]
+    fn progress_bar() {
+        let mut bar = ProgressBar::new(9);
+        bar.set_progress(0.5);
+        assert_eq!(bar.draw().to_text(), "██    50%");
+
+        bar.show_percentage(false);
+        bar.set_progress(0.3);
+        // 0.3 * 9 cells = 2.7 cells, rounded to 2 cells and 6 eighths
+        assert_eq!(bar.draw().to_text(), "██▊      ");
+
+        bar.set_progress(2.0);
+        assert_eq!(bar.get_progress(), 1.0);
+        assert_eq!(bar.draw().to_text(), "█████████");
+    }
+
+    #[test]
+    fn gauge() {
+        let mut gauge = Gauge::new(8, 3);
+        gauge.set_style(WidgetStyle {
+            highlight_bg: Color::Green,
+            ..WidgetStyle::default()
+        });
+        gauge.set_ratio(0.5);
+        let screen = gauge.draw();
+        assert_eq!(screen.to_text(), "        \n  50%   \n        ");
+        assert_eq!(screen.get_pxl(3, 2).unwrap().bg, Color::Green);
+        assert_eq!(screen.get_pxl(4, 0).unwrap().bg, Color::Reset);
+        // the label takes the colors of the part it's drawn on
+        assert_eq!(screen.get_pxl(3, 1).unwrap().bg, Color::Green);
+        assert_eq!(screen.get_pxl(4, 1).unwrap().bg, Color::Reset);
+
+        gauge.set_label(Some(String::from("1/2")));
+        assert_eq!(gauge.draw().to_text(), "        \n  1/2   \n        ");
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+
+use crate::{pixel, screen::Screen};
+
+use super::{Widget, WidgetStyle};
+
+/// Characters used to draw a fraction of a cell, from 1/8 to 8/8
+const EIGHTHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Small bar chart of the latest values of a data set
+///
+/// Each column displays one value, with a precision of an eighth of a cell.
+/// Only the last values that fit in the width of the widget are kept.
+///
+/// usage:
+/// ```
+/// use console_engine::widgets::{Sparkline, Widget};
+///
+/// let mut sparkline = Sparkline::new(30, 2);
+///
+/// // in the main loop
+/// sparkline.push(read_sensor());
+/// engine.print_screen(0, 0, sparkline.draw());
+/// ```
+pub struct Sparkline {
+    screen: Screen,
+    data: VecDeque<u64>,
+    max: Option<u64>,
+    dirty: bool,
+    style: WidgetStyle,
+}
+
+impl Sparkline {
+    /// Creates a new sparkline of the given size, without data
+    pub fn new(w: u32, h: u32) -> Self {
+        Self {
+            screen: Screen::new(w, h),
+            data: VecDeque::new(),
+            max: None,
+            dirty: true,
+            style: WidgetStyle::default(),
+        }
+    }
+
+    /// Adds a value to the right of the sparkline
+    pub fn push(&mut self, value: u64) {
+        self.dirty = true;
+        self.data.push_back(value);
+        self.truncate();
+    }
+
+    /// Replaces every value of the sparkline
+    pub fn set_data(&mut self, data: Vec<u64>) {
+        self.dirty = true;
+        self.data = data.into();
+        self.truncate();
+    }
+
+    /// Values currently displayed, oldest first
+    pub fn get_data(&self) -> Vec<u64> {
+        self.data.iter().copied().collect()
+    }
+
+    /// Sets the value displayed as a full column, or use the highest value with `None`
+    pub fn set_max(&mut self, max: Option<u64>) {
+        self.dirty = true;
+        self.max = max;
+    }
+
+    /// Changes the colors of the sparkline, bars use the foreground color
+    pub fn set_style(&mut self, style: WidgetStyle) {
+        self.dirty = true;
+        self.style = style;
+    }
+
+    fn truncate(&mut self) {
+        while self.data.len() > self.get_width() as usize {
+            self.data.pop_front();
+        }
+    }
+}
+
+impl Widget for Sparkline {
+    fn get_width(&self) -> u32 {
+        self.screen.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.screen.get_height()
+    }
+
+    fn resize(&mut self, w: u32, h: u32) {
+        self.screen.resize(w, h);
+        self.truncate();
+        self.dirty = true;
+    }
+
+    fn draw(&mut self) -> &Screen {
+        if self.dirty {
+            self.dirty = false;
+            self.screen
+                .fill(pixel::pxl_fbg(' ', self.style.fg, self.style.bg));
+            let height = self.get_height();
+            let max = self
+                .max
+                .unwrap_or_else(|| self.data.iter().copied().max().unwrap_or(0))
+                .max(1);
+            for (x, value) in self.data.iter().enumerate() {
+                let eighths = (value.min(&max) * height as u64 * 8 + max / 2) / max;
+                for row in 0..height as u64 {
+                    let level = eighths.saturating_sub(row * 8).min(8);
+                    if level > 0 {
+                        self.screen.set_pxl(
+                            x as i32,
+                            (height as u64 - 1 - row) as i32,
+                            pixel::pxl_fbg(
+                                EIGHTHS[level as usize - 1],
+                                self.style.fg,
+                                self.style.bg,
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+        &self.screen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Sparkline;
+    use crate::widgets::Widget;
+
+    #[test]
+    fn bars() {
+        let mut sparkline = Sparkline::new(4, 2);
+        sparkline.set_data(vec![0, 1, 2, 4, 8]);
+        assert_eq!(sparkline.get_data(), vec![1, 2, 4, 8]);
+        assert_eq!(sparkline.draw().to_text(), "   █\n▂▄██");
+
+        sparkline.set_max(Some(16));
+        sparkline.push(4);
+        assert_eq!(sparkline.get_data(), vec![2, 4, 8, 4]);
+        assert_eq!(sparkline.draw().to_text(), "    \n▂▄█▄");
+    }
+}
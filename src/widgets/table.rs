@@ -0,0 +1,277 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEventKind};
+use crossterm::style::Attribute;
+
+use crate::{events::Event, pixel, screen::Screen};
+
+use super::{fit, local_mouse, Selection, Widget, WidgetStyle};
+
+/// Table with a header row and selectable rows
+///
+/// Each column has a fixed width, cells are cut if they don't fit.
+/// The header stays visible while the rows are scrolled.
+/// Rows are navigated in the same way as a [ListView](struct.ListView.html).
+///
+/// usage:
+/// ```
+/// use console_engine::widgets::{Table, Widget};
+///
+/// let mut table = Table::new(30, 10, vec!["Name", "Value"], vec![20, 9]);
+/// table.push_row(vec![String::from("temperature"), String::from("21.5")]);
+///
+/// engine.print_screen(0, 0, table.draw());
+/// ```
+pub struct Table {
+    screen: Screen,
+    headers: Vec<String>,
+    widths: Vec<u32>,
+    rows: Vec<Vec<String>>,
+    selection: Selection,
+    dirty: bool,
+    active: bool,
+    position: (i32, i32),
+    style: WidgetStyle,
+}
+
+impl Table {
+    /// Creates a new table, the headers and widths define the columns
+    pub fn new(w: u32, h: u32, headers: Vec<&str>, widths: Vec<u32>) -> Self {
+        Self {
+            screen: Screen::new(w, h),
+            headers: headers.into_iter().map(String::from).collect(),
+            widths,
+            rows: vec![],
+            selection: Selection::default(),
+            dirty: true,
+            active: false,
+            position: (0, 0),
+            style: WidgetStyle::default(),
+        }
+    }
+
+    /// Number of rows that can be displayed under the header
+    fn visible_rows(&self) -> usize {
+        self.get_height().saturating_sub(1) as usize
+    }
+
+    /// Changes the headers of the table
+    pub fn set_headers(&mut self, headers: Vec<&str>) {
+        self.dirty = true;
+        self.headers = headers.into_iter().map(String::from).collect();
+    }
+
+    /// Changes the width of each column
+    pub fn set_widths(&mut self, widths: Vec<u32>) {
+        self.dirty = true;
+        self.widths = widths;
+    }
+
+    /// Retrieve the stored rows
+    pub fn get_rows(&self) -> &Vec<Vec<String>> {
+        &self.rows
+    }
+
+    /// Sets the rows of the table, keeping the selection if possible
+    pub fn set_rows(&mut self, rows: Vec<Vec<String>>) {
+        self.rows = rows;
+        let selected = self
+            .selection
+            .selected
+            .map(|s| s.min(self.rows.len().saturating_sub(1)))
+            .or(Some(0));
+        self.select(selected);
+    }
+
+    /// Adds a row at the end of the table
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+        if self.selection.selected.is_none() {
+            self.select(Some(0));
+        }
+        self.dirty = true;
+    }
+
+    /// Index of the selected row, if any
+    pub fn get_selected(&self) -> Option<usize> {
+        self.selection.selected
+    }
+
+    /// Selects a row and scrolls to it
+    pub fn select(&mut self, index: Option<usize>) {
+        self.dirty = true;
+        self.selection
+            .select(index, self.rows.len(), self.visible_rows());
+    }
+
+    /// Moves the selection up (negative) or down (positive)
+    ///
+    /// The selection is clamped at its boundaries
+    pub fn move_selection(&mut self, amount: i32) {
+        self.dirty = true;
+        self.selection
+            .move_by(amount as i64, self.rows.len(), self.visible_rows());
+    }
+
+    /// Scrolls the rows up (negative) or down (positive) without changing the selection
+    pub fn scroll(&mut self, amount: i32) {
+        self.dirty = true;
+        self.selection
+            .scroll(amount as i64, self.rows.len(), self.visible_rows());
+    }
+
+    /// Changes the colors of the table
+    pub fn set_style(&mut self, style: WidgetStyle) {
+        self.dirty = true;
+        self.style = style;
+    }
+
+    /// Builds a line of the table, separating columns with a space
+    fn format_row(&self, cells: &[String]) -> String {
+        let mut line = String::new();
+        for (id, width) in self.widths.iter().enumerate() {
+            if id > 0 {
+                line.push(' ');
+            }
+            line.push_str(&fit(
+                cells.get(id).map(|c| c.as_str()).unwrap_or(""),
+                *width as usize,
+            ));
+        }
+        fit(&line, self.get_width() as usize)
+    }
+}
+
+impl Widget for Table {
+    fn get_width(&self) -> u32 {
+        self.screen.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.screen.get_height()
+    }
+
+    fn resize(&mut self, w: u32, h: u32) {
+        self.screen.resize(w, h);
+        self.select(self.selection.selected);
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        let page = self.visible_rows().max(1) as i32;
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) if self.active => match code {
+                KeyCode::Up => self.move_selection(-1),
+                KeyCode::Down => self.move_selection(1),
+                KeyCode::PageUp => self.move_selection(-page),
+                KeyCode::PageDown => self.move_selection(page),
+                KeyCode::Home => self.move_selection(i32::MIN),
+                KeyCode::End => self.move_selection(i32::MAX),
+                _ => {}
+            },
+            Event::Mouse(mouse) => {
+                let (_, row) =
+                    match local_mouse(&mouse, self.position, self.get_width(), self.get_height()) {
+                        Some(pos) => pos,
+                        None => return,
+                    };
+                match mouse.kind {
+                    // the header can't be selected
+                    MouseEventKind::Down(MouseButton::Left) if row > 0 => {
+                        let index = self.selection.offset + row as usize - 1;
+                        if index < self.rows.len() {
+                            self.select(Some(index));
+                        }
+                    }
+                    MouseEventKind::ScrollUp => self.scroll(-1),
+                    MouseEventKind::ScrollDown => self.scroll(1),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.dirty = true;
+        self.active = active;
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn set_position(&mut self, x: i32, y: i32) {
+        self.position = (x, y);
+    }
+
+    fn draw(&mut self) -> &Screen {
+        if self.dirty {
+            self.dirty = false;
+            self.screen
+                .fill(pixel::pxl_fbg(' ', self.style.fg, self.style.bg));
+            let header = self.format_row(&self.headers);
+            self.screen.print_fbg_attr(
+                0,
+                0,
+                &header,
+                self.style.fg,
+                self.style.bg,
+                Attribute::Bold.into(),
+            );
+            for (row, (id, cells)) in self
+                .rows
+                .iter()
+                .enumerate()
+                .skip(self.selection.offset)
+                .take(self.visible_rows())
+                .enumerate()
+            {
+                let (fg, bg) = if self.selection.selected == Some(id) {
+                    (self.style.highlight_fg, self.style.highlight_bg)
+                } else {
+                    (self.style.fg, self.style.bg)
+                };
+                let line = self.format_row(cells);
+                self.screen.print_fbg(0, row as i32 + 1, &line, fg, bg);
+            }
+        }
+        &self.screen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use crossterm::style::Attribute;
+
+    use super::Table;
+    use crate::events::Event;
+    use crate::widgets::Widget;
+
+    #[test]
+    fn columns() {
+        let mut table = Table::new(12, 3, vec!["Name", "Value"], vec![5, 4]);
+        table.push_row(vec![String::from("temperature"), String::from("21.5")]);
+        table.push_row(vec![String::from("wind")]);
+        table.push_row(vec![String::from("rain"), String::from("0")]);
+        assert_eq!(
+            table.draw().to_text(),
+            "Name  Valu  \ntempe 21.5  \nwind        "
+        );
+        assert_eq!(
+            table.draw().get_pxl(0, 0).unwrap().attr,
+            Attribute::Bold.into()
+        );
+
+        // the header stays while the rows scroll
+        table.set_active(true);
+        table.handle_event(Event::Key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE)));
+        assert_eq!(table.get_selected(), Some(2));
+        assert_eq!(
+            table.draw().to_text(),
+            "Name  Valu  \nwind        \nrain  0     "
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEventKind};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{events::Event, pixel, screen::Screen};
+
+use super::{local_mouse, Widget, WidgetStyle};
+
+/// Tab bar
+///
+/// Use the left and right arrow keys to change the selected tab while the bar is active,
+/// or click on a tab to select it.
+///
+/// usage:
+/// ```
+/// use console_engine::widgets::{Tabs, Widget};
+///
+/// let mut tabs = Tabs::new(40, vec!["Overview", "Sensors", "Logs"]);
+/// tabs.set_active(true);
+///
+/// // in the main loop
+/// tabs.handle_event(event);
+/// engine.print_screen(0, 0, tabs.draw());
+/// match tabs.get_selected() {
+///     0 => { /* draw the overview */ }
+///     // ...
+/// }
+/// ```
+pub struct Tabs {
+    screen: Screen,
+    titles: Vec<String>,
+    selected: usize,
+    separator: String,
+    dirty: bool,
+    active: bool,
+    position: (i32, i32),
+    style: WidgetStyle,
+}
+
+impl Tabs {
+    /// Creates a new tab bar, the first tab being selected
+    pub fn new(w: u32, titles: Vec<&str>) -> Self {
+        Self {
+            screen: Screen::new(w, 1),
+            titles: titles.into_iter().map(String::from).collect(),
+            selected: 0,
+            separator: String::from(" | "),
+            dirty: true,
+            active: false,
+            position: (0, 0),
+            style: WidgetStyle::default(),
+        }
+    }
+
+    /// Retrieve the titles of the tabs
+    pub fn get_titles(&self) -> &Vec<String> {
+        &self.titles
+    }
+
+    /// Changes the titles of the tabs, keeping the selection if possible
+    pub fn set_titles(&mut self, titles: Vec<&str>) {
+        self.titles = titles.into_iter().map(String::from).collect();
+        self.select(self.selected);
+    }
+
+    /// Changes the string drawn between tabs (`" | "` by default)
+    pub fn set_separator(&mut self, separator: &str) {
+        self.dirty = true;
+        self.separator = String::from(separator);
+    }
+
+    /// Index of the selected tab
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects a tab, the index is clamped to the existing tabs
+    pub fn select(&mut self, index: usize) {
+        self.dirty = true;
+        self.selected = index.min(self.titles.len().saturating_sub(1));
+    }
+
+    /// Selects the next tab, going back to the first one after the last tab
+    pub fn next(&mut self) {
+        if !self.titles.is_empty() {
+            self.select((self.selected + 1) % self.titles.len());
+        }
+    }
+
+    /// Selects the previous tab, going to the last one before the first tab
+    pub fn previous(&mut self) {
+        if !self.titles.is_empty() {
+            self.select((self.selected + self.titles.len() - 1) % self.titles.len());
+        }
+    }
+
+    /// Changes the colors of the bar
+    pub fn set_style(&mut self, style: WidgetStyle) {
+        self.dirty = true;
+        self.style = style;
+    }
+
+    /// Horizontal range covered by each tab
+    fn tab_ranges(&self) -> Vec<(u32, u32)> {
+        let mut ranges = vec![];
+        let mut x = 0;
+        for title in self.titles.iter() {
+            let width = title.width() as u32;
+            ranges.push((x, x + width));
+            x += width + self.separator.width() as u32;
+        }
+        ranges
+    }
+}
+
+impl Widget for Tabs {
+    fn get_width(&self) -> u32 {
+        self.screen.get_width()
+    }
+
+    fn get_height(&self) -> u32 {
+        self.screen.get_height()
+    }
+
+    fn resize(&mut self, w: u32, _: u32) {
+        self.screen.resize(w, 1);
+        self.dirty = true;
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) if self.active => match code {
+                KeyCode::Left => self.previous(),
+                KeyCode::Right => self.next(),
+                _ => {}
+            },
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                if let Some((x, _)) =
+                    local_mouse(&mouse, self.position, self.get_width(), self.get_height())
+                {
+                    if let Some(index) = self
+                        .tab_ranges()
+                        .iter()
+                        .position(|(start, end)| x >= *start && x < *end)
+                    {
+                        self.select(index);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.dirty = true;
+        self.active = active;
+    }
+
+    fn is_active(&self) -> bool {
+        self.active
+    }
+
+    fn set_position(&mut self, x: i32, y: i32) {
+        self.position = (x, y);
+    }
+
+    fn draw(&mut self) -> &Screen {
+        if self.dirty {
+            self.dirty = false;
+            self.screen
+                .fill(pixel::pxl_fbg(' ', self.style.fg, self.style.bg));
+            for (id, (title, (start, _))) in self.titles.iter().zip(self.tab_ranges()).enumerate() {
+                if id > 0 {
+                    self.screen.print_fbg(
+                        start as i32 - self.separator.width() as i32,
+                        0,
+                        &self.separator,
+                        self.style.fg,
+                        self.style.bg,
+                    );
+                }
+                let (fg, bg) = if id == self.selected {
+                    (self.style.highlight_fg, self.style.highlight_bg)
+                } else {
+                    (self.style.fg, self.style.bg)
+                };
+                self.screen.print_fbg(start as i32, 0, title, fg, bg);
+            }
+        }
+        &self.screen
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::{
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+
+    use super::Tabs;
+    use crate::events::Event;
+    use crate::widgets::Widget;
+
+    #[test]
+    fn selection() {
+        let mut tabs = Tabs::new(16, vec!["one", "two", "three"]);
+        assert_eq!(tabs.draw().to_text(), "one | two | thre");
+
+        tabs.set_active(true);
+        tabs.handle_event(Event::Key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE)));
+        assert_eq!(tabs.get_selected(), 2);
+        tabs.handle_event(Event::Key(KeyEvent::new(
+            KeyCode::Right,
+            KeyModifiers::NONE,
+        )));
+        assert_eq!(tabs.get_selected(), 0);
+
+        tabs.set_position(0, 3);
+        let click = |column| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row: 3,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+        tabs.handle_event(click(7));
+        assert_eq!(tabs.get_selected(), 1);
+        // on a separator
+        tabs.handle_event(click(4));
+        assert_eq!(tabs.get_selected(), 1);
+    }
+}